@@ -0,0 +1,272 @@
+//! Blocking DMA transfers.
+//!
+//! The functions in this module perform the same transfers as the DMA futures,
+//! but they don't need an executor. [`memcpy()`], [`read`], [`write()`], and
+//! [`full_duplex`] start a transfer, then spin until the transfer completes.
+//!
+//! For cooperative polling, use the `start_*` functions. They prepare a transfer
+//! and return a [`Handle`]. Pin the handle in place, for example with
+//! [`pin!`](core::pin::pin), then check it with [`is_done`](Handle::is_done) or
+//! [`try_wait`](Handle::try_wait), which follow the [`nb`](https://docs.rs/nb)
+//! conventions. The first check starts the transfer. To cancel the transfer, drop
+//! the handle.
+//!
+//! # Example
+//!
+//! Copy a buffer, then poll a second copy with a budget.
+//!
+//! ```no_run
+//! use core::pin::pin;
+//! use imxrt_dma::blocking;
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//!
+//! let mut channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//!
+//! let source = [4u32, 5, 6, 7, 8];
+//! let mut destination = [0; 5];
+//! blocking::memcpy(&source, &mut destination, &mut channel).unwrap();
+//!
+//! let mut handle = pin!(blocking::start_memcpy(&source, &mut destination, &mut channel));
+//! match handle.as_mut().wait_budget(1_000) {
+//!     Ok(()) => { /* Done! */ }
+//!     Err(nb::Error::WouldBlock) => { /* Still going. Try again later, or drop to cancel. */ }
+//!     Err(nb::Error::Other(err)) => panic!("{err}"),
+//! }
+//! ```
+
+use crate::{
+    channel::Channel,
+    memcpy::{self, Memcpy},
+    peripheral::{self, Bidirectional, Destination, FullDuplex, Read, Source, Write},
+    Element, Error,
+};
+
+use core::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll, Waker},
+};
+
+/// A handle to a DMA transfer
+///
+/// Use one of the `start_*` functions to create a handle. The handle drives
+/// the transfer without an executor. Pin the handle before you check it; the
+/// first check starts the transfer. Dropping the handle cancels the transfer.
+pub struct Handle<F: Future> {
+    future: F,
+    output: Option<F::Output>,
+}
+
+impl<F, T> Handle<F>
+where
+    F: Future<Output = Result<T, Error>>,
+    T: Copy,
+{
+    /// Wrap the transfer described by `future`
+    fn new(future: F) -> Self {
+        Handle {
+            future,
+            output: None,
+        }
+    }
+
+    /// Poll the transfer once, unless it already finished
+    fn poll(self: Pin<&mut Self>) -> Option<Result<T, Error>> {
+        // Safety: pin projection OK. The future is never moved, and
+        // the output is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.output.is_none() {
+            // Safety: see above.
+            let future = unsafe { Pin::new_unchecked(&mut this.future) };
+            let mut cx = Context::from_waker(Waker::noop());
+            if let Poll::Ready(output) = future.poll(&mut cx) {
+                this.output = Some(output);
+            }
+        }
+        this.output
+    }
+
+    /// Returns `true` if the transfer completed, or produced an error
+    pub fn is_done(self: Pin<&mut Self>) -> bool {
+        self.poll().is_some()
+    }
+
+    /// Check the transfer without blocking
+    ///
+    /// Returns `WouldBlock` if the transfer is still in progress. Once the
+    /// transfer finishes, this returns the same result for every call.
+    pub fn try_wait(self: Pin<&mut Self>) -> nb::Result<T, Error> {
+        match self.poll() {
+            Some(result) => result.map_err(nb::Error::Other),
+            None => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    /// Check the transfer up to `budget` times, returning as soon as it finishes
+    ///
+    /// The budget counts checks, not time or CPU cycles. How long a check
+    /// takes depends on the transfer and the CPU.
+    ///
+    /// Returns `WouldBlock` if the transfer is still in progress after
+    /// `budget` checks.
+    pub fn wait_budget(mut self: Pin<&mut Self>, budget: u32) -> nb::Result<T, Error> {
+        for _ in 0..budget {
+            match self.as_mut().try_wait() {
+                Err(nb::Error::WouldBlock) => continue,
+                result => return result,
+            }
+        }
+        self.try_wait()
+    }
+
+    /// Spin until the transfer finishes
+    pub fn wait(mut self: Pin<&mut Self>) -> Result<T, Error> {
+        loop {
+            if let Some(result) = self.as_mut().poll() {
+                return result;
+            }
+        }
+    }
+}
+
+/// Prepare a DMA-powered memcpy between the `source` and `destination` buffers
+///
+/// See [`memcpy::memcpy`] for more information.
+pub fn start_memcpy<'a, E: Element>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    channel: &'a mut Channel,
+) -> Handle<Memcpy<'a, E>> {
+    Handle::new(memcpy::memcpy(source, destination, channel))
+}
+
+/// Perform a DMA-powered memcpy between the `source` and `destination` buffers,
+/// blocking until the transfer completes
+///
+/// See [`memcpy::memcpy`] for more information.
+pub fn memcpy<E: Element>(
+    source: &[E],
+    destination: &mut [E],
+    channel: &mut Channel,
+) -> Result<(), Error> {
+    pin!(start_memcpy(source, destination, channel)).wait()
+}
+
+/// Prepare to receive a `buffer` of elements from the source peripheral
+///
+/// See [`peripheral::read`] for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the source's window size.
+pub fn start_read<'a, S, E>(
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [E],
+) -> Handle<Read<'a, S, E>>
+where
+    S: Source<E>,
+    E: Element,
+{
+    Handle::new(peripheral::read(channel, source, buffer))
+}
+
+/// Receive a `buffer` of elements from the source peripheral, blocking until
+/// the transfer completes
+///
+/// See [`peripheral::read`] for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the source's window size.
+pub fn read<S, E>(channel: &mut Channel, source: &mut S, buffer: &mut [E]) -> Result<(), Error>
+where
+    S: Source<E>,
+    E: Element,
+{
+    pin!(start_read(channel, source, buffer)).wait()
+}
+
+/// Prepare to send a `buffer` of data to the destination peripheral
+///
+/// See [`peripheral::write`] for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the destination's window size.
+pub fn start_write<'a, D, E>(
+    channel: &'a mut Channel,
+    buffer: &'a [E],
+    destination: &'a mut D,
+) -> Handle<Write<'a, D, E>>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    Handle::new(peripheral::write(channel, buffer, destination))
+}
+
+/// Send a `buffer` of data to the destination peripheral, blocking until
+/// the transfer completes
+///
+/// See [`peripheral::write`] for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the destination's window size.
+pub fn write<D, E>(channel: &mut Channel, buffer: &[E], destination: &mut D) -> Result<(), Error>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    pin!(start_write(channel, buffer, destination)).wait()
+}
+
+/// Prepare a full-duplex transfer that reads and writes from a single buffer
+///
+/// See [`peripheral::full_duplex`] for more
+/// information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the peripheral's window sizes.
+pub fn start_full_duplex<'a, P, E>(
+    rx_channel: &'a mut Channel,
+    tx_channel: &'a mut Channel,
+    peripheral: &'a mut P,
+    buffer: &'a mut [E],
+) -> Handle<FullDuplex<'a, P, E>>
+where
+    P: Bidirectional<E>,
+    E: Element,
+{
+    Handle::new(peripheral::full_duplex(
+        rx_channel, tx_channel, peripheral, buffer,
+    ))
+}
+
+/// Perform a full-duplex transfer that reads and writes from a single buffer,
+/// blocking until the transfer completes
+///
+/// See [`peripheral::full_duplex`] for more
+/// information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the peripheral's window sizes.
+pub fn full_duplex<P, E>(
+    rx_channel: &mut Channel,
+    tx_channel: &mut Channel,
+    peripheral: &mut P,
+    buffer: &mut [E],
+) -> Result<(), Error>
+where
+    P: Bidirectional<E>,
+    E: Element,
+{
+    pin!(start_full_duplex(
+        rx_channel, tx_channel, peripheral, buffer
+    ))
+    .wait()
+}
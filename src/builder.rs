@@ -0,0 +1,442 @@
+//! A safe builder for custom DMA transfers.
+//!
+//! A [`Builder`] describes a transfer's source and destination, its loop shape,
+//! and its completion behavior. [`build`](Builder::build) checks that the
+//! description is consistent, then programs a DMA channel. The result is a
+//! [`Prepared`] transfer that borrows the buffers. Use [`run`](Prepared::run)
+//! to run the transfer.
+//!
+//! The builder knows the length of every buffer, so it's safe to use. To describe
+//! a source or destination that isn't a buffer, like a peripheral register, use
+//! the unsafe [`source_address`](Builder::source_address) and
+//! [`destination_address`](Builder::destination_address) methods.
+//!
+//! # Example
+//!
+//! Fill a buffer with a constant, moving four elements per minor loop. Without
+//! a hardware trigger, the channel runs all 16 minor loops back to back.
+//!
+//! ```no_run
+//! use imxrt_dma::builder::Builder;
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//!
+//! # async fn f() -> imxrt_dma::Result<()> {
+//! let mut channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! let fill = 0xDEAD_BEEFu32;
+//! let mut buffer = [0u32; 64];
+//!
+//! let mut prepared = Builder::new()
+//!     .source_element(&fill)
+//!     .destination_buffer(&mut buffer)
+//!     .minor_loop(4)
+//!     .build(&mut channel)
+//!     .unwrap();
+//! prepared.run().await?;
+//! # Ok(()) }
+//! ```
+
+use crate::{
+    cache,
+    channel::{Channel, Configuration, Descriptor},
+    interrupt::Transfer,
+    BandwidthControl, Element, Error,
+};
+
+use core::{
+    cell::Cell,
+    fmt::{self, Display},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The maximum number of major loop iterations
+const MAX_ITERATIONS: u16 = 0x7FFF;
+
+/// One side of a transfer
+#[derive(Clone, Copy)]
+struct Side {
+    address: u32,
+    /// Signed offset, in bytes, applied after each element.
+    offset: i16,
+    /// Number of elements that the side can hold, if known.
+    len: Option<usize>,
+}
+
+/// Describes a DMA transfer
+///
+/// See the [module-level documentation](crate::builder) for more information.
+pub struct Builder<'a, E> {
+    source: Option<Side>,
+    destination: Option<Side>,
+    source_buffer: Option<&'a [E]>,
+    destination_buffer: Option<&'a mut [E]>,
+    minor_loop: u32,
+    iterations: Option<u16>,
+    configuration: Configuration,
+    interrupt_on_completion: bool,
+    bandwidth_control: Option<BandwidthControl>,
+    _elem: PhantomData<&'a mut E>,
+}
+
+impl<E: Element> Default for Builder<'_, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, E: Element> Builder<'a, E> {
+    /// Create a builder with no source or destination
+    ///
+    /// By default, each minor loop moves one element, the number of
+    /// iterations is derived from the buffer lengths, and the transfer runs
+    /// as soon as the channel is enabled.
+    pub const fn new() -> Self {
+        Builder {
+            source: None,
+            destination: None,
+            source_buffer: None,
+            destination_buffer: None,
+            minor_loop: 1,
+            iterations: None,
+            configuration: Configuration::Off,
+            interrupt_on_completion: false,
+            bandwidth_control: None,
+            _elem: PhantomData,
+        }
+    }
+
+    /// Read elements from `buffer`, starting at the first element
+    pub fn source_buffer(mut self, buffer: &'a [E]) -> Self {
+        self.source = Some(Side {
+            address: buffer.as_ptr() as u32,
+            offset: core::mem::size_of::<E>() as i16,
+            len: Some(buffer.len()),
+        });
+        self.source_buffer = Some(buffer);
+        self
+    }
+
+    /// Read the same `element` for every transfer
+    pub fn source_element(mut self, element: &'a E) -> Self {
+        self.source = Some(Side {
+            address: element as *const E as u32,
+            offset: 0,
+            len: None,
+        });
+        self.source_buffer = None;
+        self
+    }
+
+    /// Read elements from `address`, adding `offset` bytes after each element
+    ///
+    /// # Safety
+    ///
+    /// Every address reached by the transfer must be valid to read for `'a`.
+    /// If `offset` is zero, `address` may be a peripheral register.
+    pub unsafe fn source_address(mut self, address: *const E, offset: i16) -> Self {
+        self.source = Some(Side {
+            address: address as u32,
+            offset,
+            len: None,
+        });
+        self.source_buffer = None;
+        self
+    }
+
+    /// Write elements into `buffer`, starting at the first element
+    pub fn destination_buffer(mut self, buffer: &'a mut [E]) -> Self {
+        self.destination = Some(Side {
+            address: buffer.as_ptr() as u32,
+            offset: core::mem::size_of::<E>() as i16,
+            len: Some(buffer.len()),
+        });
+        self.destination_buffer = Some(buffer);
+        self
+    }
+
+    /// Write every element into `element`
+    pub fn destination_element(mut self, element: &'a mut E) -> Self {
+        self.destination = Some(Side {
+            address: element as *mut E as u32,
+            offset: 0,
+            len: None,
+        });
+        self.destination_buffer = None;
+        self
+    }
+
+    /// Write elements to `address`, adding `offset` bytes after each element
+    ///
+    /// # Safety
+    ///
+    /// Every address reached by the transfer must be valid to write for `'a`.
+    /// If `offset` is zero, `address` may be a peripheral register.
+    pub unsafe fn destination_address(mut self, address: *mut E, offset: i16) -> Self {
+        self.destination = Some(Side {
+            address: address as u32,
+            offset,
+            len: None,
+        });
+        self.destination_buffer = None;
+        self
+    }
+
+    /// Move `elements` for each minor loop
+    ///
+    /// The default is one element.
+    pub fn minor_loop(mut self, elements: u32) -> Self {
+        self.minor_loop = elements;
+        self
+    }
+
+    /// Run `iterations` minor loops
+    ///
+    /// By default, the iterations are derived from the buffer lengths. If
+    /// neither side is a buffer, you must specify the iterations.
+    pub fn iterations(mut self, iterations: u16) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    /// Set the DMAMUX channel configuration
+    ///
+    /// The default is [`Configuration::Off`]: no hardware triggers the transfer.
+    /// The channel then runs on an [`AlwaysOn`](Configuration::AlwaysOn) request,
+    /// and it runs every minor loop once it's enabled. Use another configuration
+    /// for hardware-triggered transfers.
+    pub fn configuration(mut self, configuration: Configuration) -> Self {
+        self.configuration = configuration;
+        self
+    }
+
+    /// Interrupt when the transfer completes
+    ///
+    /// The default is `false`.
+    pub fn interrupt_on_completion(mut self, intr: bool) -> Self {
+        self.interrupt_on_completion = intr;
+        self
+    }
+
+    /// Set the channel's bandwidth control
+    ///
+    /// The default is `None`.
+    pub fn bandwidth_control(mut self, bandwidth: Option<BandwidthControl>) -> Self {
+        self.bandwidth_control = bandwidth;
+        self
+    }
+
+    /// Check the description, then program `channel` with the transfer
+    ///
+    /// Once the transfer completes, the channel's addresses return to the
+    /// start of the transfer. So you may run the prepared transfer more than
+    /// once.
+    pub fn build(self, channel: &'a mut Channel) -> Result<Prepared<'a, E>, BuildError> {
+        let source = self.source.ok_or(BuildError::MissingSource)?;
+        let destination = self.destination.ok_or(BuildError::MissingDestination)?;
+
+        let minor_bytes = self
+            .minor_loop
+            .checked_mul(core::mem::size_of::<E>() as u32)
+            .filter(|&bytes| bytes != 0)
+            .ok_or(BuildError::MinorLoop)?;
+        let minor_loop = self.minor_loop as usize;
+
+        let iterations = match self.iterations {
+            Some(iterations) => iterations as usize,
+            None => source
+                .len
+                .into_iter()
+                .chain(destination.len)
+                .min()
+                .ok_or(BuildError::Iterations)?
+                .checked_div(minor_loop)
+                .unwrap_or(0),
+        };
+        if iterations == 0 || iterations > MAX_ITERATIONS as usize {
+            return Err(BuildError::Iterations);
+        }
+        let elements = iterations * minor_loop;
+        if source.len.is_some_and(|len| len < elements) {
+            return Err(BuildError::SourceLength);
+        }
+        if destination.len.is_some_and(|len| len < elements) {
+            return Err(BuildError::DestinationLength);
+        }
+        if let Configuration::Enable { periodic: true, .. } = self.configuration {
+            if channel.channel() >= 4 {
+                return Err(BuildError::Configuration);
+            }
+        }
+
+        let rewind = |side: &Side| {
+            (side.offset as i32)
+                .wrapping_mul(elements as i32)
+                .wrapping_neg()
+        };
+
+        channel.disable();
+        channel.set_disable_on_completion(true);
+        channel.set_interrupt_on_completion(self.interrupt_on_completion);
+        channel.set_bandwidth_control(self.bandwidth_control);
+        // Without a hardware trigger, a software start would only run one minor
+        // loop. An always-on request runs them all.
+        channel.set_channel_configuration(match self.configuration {
+            Configuration::Off => Configuration::AlwaysOn,
+            configuration => configuration,
+        });
+        // Safety: the buffers are borrowed for 'a, and the checks above make
+        // sure that the transfer stays within them. Raw addresses are covered
+        // by the caller's contract.
+        unsafe {
+            channel.set_source_address(source.address as *const E);
+            channel.set_source_offset(source.offset);
+            channel.set_source_attributes::<E>(0);
+            channel.set_source_last_address_adjustment(rewind(&source));
+            channel.set_destination_address(destination.address as *const E);
+            channel.set_destination_offset(destination.offset);
+            channel.set_destination_attributes::<E>(0);
+            channel.set_destination_last_address_adjustment(rewind(&destination));
+            channel.set_minor_loop_bytes(minor_bytes);
+            channel.set_transfer_iterations(iterations as u16);
+        }
+
+        Ok(Prepared {
+            descriptor: channel.descriptor(),
+            dirty: Cell::new(false),
+            channel,
+            source: self.source_buffer,
+            destination: self.destination_buffer,
+        })
+    }
+}
+
+/// An inconsistent transfer description
+///
+/// Returned by [`Builder::build`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum BuildError {
+    /// There's no source.
+    MissingSource,
+    /// There's no destination.
+    MissingDestination,
+    /// The minor loop is empty, or it's too large.
+    MinorLoop,
+    /// The iterations are zero, too large, or they can't be derived
+    /// from a buffer.
+    Iterations,
+    /// The transfer reads beyond the source buffer.
+    SourceLength,
+    /// The transfer writes beyond the destination buffer.
+    DestinationLength,
+    /// The channel doesn't support the DMAMUX configuration.
+    Configuration,
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            BuildError::MissingSource => "missing source",
+            BuildError::MissingDestination => "missing destination",
+            BuildError::MinorLoop => "invalid minor loop size",
+            BuildError::Iterations => "invalid iterations",
+            BuildError::SourceLength => "transfer exceeds the source buffer",
+            BuildError::DestinationLength => "transfer exceeds the destination buffer",
+            BuildError::Configuration => "unsupported DMAMUX configuration",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// A transfer that's programmed into a DMA channel
+///
+/// Use a [`Builder`] to create a `Prepared` transfer. The prepared transfer
+/// borrows the channel, and the buffers, for as long as it exists.
+pub struct Prepared<'a, E> {
+    channel: &'a mut Channel,
+    source: Option<&'a [E]>,
+    destination: Option<&'a mut [E]>,
+    /// The programmed transfer, restored before a run if needed.
+    descriptor: Descriptor,
+    /// Set while a run is incomplete, or if it didn't complete.
+    dirty: Cell<bool>,
+}
+
+impl<E: Element> Prepared<'_, E> {
+    /// Returns the source buffer, if the source is a buffer
+    pub fn source(&self) -> Option<&[E]> {
+        self.source
+    }
+
+    /// Returns the destination buffer, if the destination is a buffer
+    pub fn destination(&mut self) -> Option<&mut [E]> {
+        self.destination.as_deref_mut()
+    }
+
+    /// Run the transfer
+    ///
+    /// Each run enables the already-programmed channel, so it's cheap to run
+    /// the same transfer many times. A run starts from the beginning of the
+    /// buffers. If the previous run didn't complete, the run first restores
+    /// the programmed transfer.
+    ///
+    /// The returned future enables the channel, and resolves when the transfer
+    /// completes. If the channel [maintains the data cache](Channel::set_cache_maintenance),
+    /// the source buffer is cleaned, and the destination buffer is invalidated
+    /// when the transfer ends.
+    pub fn run(&mut self) -> Run<'_> {
+        if self.dirty.replace(true) {
+            // Safety: build programmed the descriptor, and the buffers are
+            // borrowed by self.
+            unsafe { self.channel.set_descriptor(&self.descriptor) };
+        }
+        if let Some(source) = self.source {
+            cache::prepare_source(self.channel, source);
+        }
+        let invalidate = match self.destination.as_deref_mut() {
+            Some(destination) => {
+                cache::prepare_destination(self.channel, destination);
+                cache::Invalidate::new(self.channel, destination)
+            }
+            None => cache::Invalidate::NONE,
+        };
+        let channel: &Channel = self.channel;
+        Run {
+            // Safety: the builder programmed a valid transfer, and the
+            // buffers are borrowed by self.
+            transfer: unsafe { Transfer::new(channel) },
+            _cache: invalidate,
+            dirty: &self.dirty,
+        }
+    }
+}
+
+/// A future that runs a [`Prepared`] transfer
+///
+/// Use [`Prepared::run`] to create this future.
+pub struct Run<'a> {
+    transfer: Transfer<'a>,
+    _cache: cache::Invalidate,
+    /// Cleared when the run completes.
+    dirty: &'a Cell<bool>,
+}
+
+impl Future for Run<'_> {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let dirty = self.dirty;
+        // Safety: data not moved
+        let poll = unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx);
+        if let Poll::Ready(Ok(())) = poll {
+            dirty.set(false);
+        }
+        poll
+    }
+}
+
+// Drop handled by Transfer and Invalidate impls
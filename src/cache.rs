@@ -0,0 +1,212 @@
+//! Data cache maintenance for DMA buffers
+//!
+//! Some i.MX RT processors, like the 1050, 1060, and 1170, have a Cortex-M7
+//! data cache. The DMA controller does not see the data cache. If a DMA buffer
+//! is in cacheable memory, the CPU and the DMA controller may see different
+//! data, unless software maintains the cache:
+//!
+//! - before a DMA channel reads a buffer, *clean* the buffer, so that the DMA
+//!   channel sees the CPU's writes.
+//! - after a DMA channel writes a buffer, *invalidate* the buffer, so that the
+//!   CPU sees the DMA channel's writes.
+//!
+//! The safe DMA futures perform this maintenance if you opt in with
+//! [`Channel::set_cache_maintenance`](crate::channel::Channel::set_cache_maintenance).
+//! You may also use the functions in this module to maintain the cache yourself.
+//!
+//! # Alignment
+//!
+//! Cache maintenance works on [`LINE_SIZE`] cache lines. A destination buffer
+//! should start and end on a cache line boundary; see [`is_aligned`] and
+//! [`Aligned`]. If a destination buffer shares a cache line with other data,
+//! [`invalidate`] falls back to cleaning *and* invalidating the shared line.
+//! Don't access any data that shares a cache line with a destination buffer
+//! while a DMA transfer is in progress; the fallback may overwrite the DMA
+//! channel's writes with your data, or your writes with the DMA channel's
+//! data.
+//!
+//! [`memcpy_bytes`](crate::memcpy::memcpy_bytes) copies unaligned bytes at the
+//! start and end of its buffers with the CPU. Those bytes share cache lines with
+//! the DMA channel's part of the destination, and the same rules apply to them.
+//!
+//! # Cores without a data cache
+//!
+//! The maintenance operations write the Cortex-M7 cache maintenance registers.
+//! The default `dcache` feature enables them on ARM targets. On targets without
+//! a data cache, like the i.MX RT 1170's Cortex-M4, disable default features;
+//! the maintenance operations then have no effect. They never have an effect
+//! on non-ARM targets.
+
+use crate::{channel::Channel, Element};
+
+/// The size, in bytes, of a data cache line
+pub const LINE_SIZE: usize = 32;
+
+/// Aligns `T` to a cache line
+///
+/// Use `Aligned` to make sure a DMA destination buffer doesn't share a cache
+/// line with other data.
+///
+/// ```
+/// use imxrt_dma::cache::{self, Aligned};
+///
+/// static mut BUFFER: Aligned<[u8; 64]> = Aligned([0; 64]);
+/// # let buffer = unsafe { &mut *core::ptr::addr_of_mut!(BUFFER) };
+/// assert!(cache::is_aligned(&buffer.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(C, align(32))]
+pub struct Aligned<T>(pub T);
+
+const _STATIC_ASSERT_ALIGNED_IS_LINE_SIZE: [u32; 1] =
+    [0; (LINE_SIZE == core::mem::align_of::<Aligned<u8>>()) as usize];
+
+/// Returns `true` if `buffer` starts and ends on a cache line boundary
+pub fn is_aligned<T>(buffer: &[T]) -> bool {
+    let start = buffer.as_ptr() as usize;
+    let len = core::mem::size_of_val(buffer);
+    start.is_multiple_of(LINE_SIZE) && len.is_multiple_of(LINE_SIZE)
+}
+
+/// Clean all cache lines that hold `buffer`
+///
+/// Use this before a DMA channel reads `buffer`.
+pub fn clean<E: Element>(buffer: &[E]) {
+    clean_range(buffer.as_ptr() as usize, core::mem::size_of_val(buffer));
+}
+
+/// Clean, then invalidate, all cache lines that hold `buffer`
+///
+/// Use this before a DMA channel writes `buffer`. This makes sure that the
+/// cache doesn't evict dirty data into `buffer` during the transfer.
+pub fn clean_invalidate<E: Element>(buffer: &mut [E]) {
+    clean_invalidate_range(buffer.as_ptr() as usize, core::mem::size_of_val(buffer));
+}
+
+/// Invalidate all cache lines that hold `buffer`
+///
+/// Use this after a DMA channel writes `buffer`. Any of the CPU's writes
+/// to `buffer` that weren't cleaned are discarded.
+///
+/// If `buffer` shares a cache line with other data, the shared line is
+/// cleaned and invalidated. See the [module-level documentation](crate::cache)
+/// for the implications.
+pub fn invalidate<E: Element>(buffer: &mut [E]) {
+    invalidate_range(buffer.as_ptr() as usize, core::mem::size_of_val(buffer));
+}
+
+/// Prepare a buffer that a DMA channel will read
+pub(crate) fn prepare_source<E: Element>(channel: &Channel, buffer: &[E]) {
+    if channel.cache_maintenance() {
+        clean(buffer);
+    }
+}
+
+/// Prepare a buffer that a DMA channel will write
+pub(crate) fn prepare_destination<E: Element>(channel: &Channel, buffer: &mut [E]) {
+    if channel.cache_maintenance() {
+        clean_invalidate(buffer);
+    }
+}
+
+/// Invalidates a DMA destination when dropped
+///
+/// Place this after the `Transfer` in a future, so that the transfer is
+/// stopped before the invalidation.
+pub(crate) struct Invalidate {
+    range: Option<(usize, usize)>,
+}
+
+impl Invalidate {
+    /// Invalidate nothing
+    pub(crate) const NONE: Self = Invalidate { range: None };
+
+    /// Invalidate `buffer`, if `channel` performs cache maintenance
+    pub(crate) fn new<E: Element>(channel: &Channel, buffer: &mut [E]) -> Self {
+        Invalidate {
+            range: channel
+                .cache_maintenance()
+                .then(|| (buffer.as_ptr() as usize, core::mem::size_of_val(buffer))),
+        }
+    }
+}
+
+impl Invalidate {
+    /// Invalidate the destination now, instead of when dropped
+    ///
+    /// The DMA channel must be stopped. Later calls, and the drop,
+    /// have no effect.
+    pub(crate) fn invalidate(&mut self) {
+        if let Some((start, len)) = self.range.take() {
+            invalidate_range(start, len);
+        }
+    }
+}
+
+impl Drop for Invalidate {
+    fn drop(&mut self) {
+        self.invalidate();
+    }
+}
+
+/// Data cache clean by address to the point of coherency
+const DCCMVAC: usize = 0xE000_EF68;
+/// Data cache invalidate by address to the point of coherency
+const DCIMVAC: usize = 0xE000_EF5C;
+/// Data cache clean and invalidate by address to the point of coherency
+const DCCIMVAC: usize = 0xE000_EF70;
+
+pub(crate) fn clean_range(start: usize, len: usize) {
+    maintain(DCCMVAC, start, len);
+}
+
+fn clean_invalidate_range(start: usize, len: usize) {
+    maintain(DCCIMVAC, start, len);
+}
+
+fn invalidate_range(start: usize, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let end = start + len;
+    let mut first = start & !(LINE_SIZE - 1);
+    let mut last = (end + LINE_SIZE - 1) & !(LINE_SIZE - 1);
+
+    // Partial lines at either end hold someone else's data.
+    if first != start {
+        maintain(DCCIMVAC, first, 1);
+        first += LINE_SIZE;
+    }
+    if last != end && last > first {
+        maintain(DCCIMVAC, last - LINE_SIZE, 1);
+        last -= LINE_SIZE;
+    }
+    if last > first {
+        maintain(DCIMVAC, first, last - first);
+    }
+}
+
+/// Write every cache line in `[start, start + len)` to the maintenance register
+#[cfg(all(target_arch = "arm", feature = "dcache"))]
+fn maintain(register: usize, start: usize, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let register = register as *mut u32;
+    let end = start + len;
+    let mut line = start & !(LINE_SIZE - 1);
+
+    cortex_m::asm::dsb();
+    while line < end {
+        // Safety: write-only system control register, valid on all
+        // Cortex-M7 processors. Maintenance has no effect when the
+        // cache is disabled.
+        unsafe { register.write_volatile(line as u32) };
+        line += LINE_SIZE;
+    }
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+}
+
+#[cfg(not(all(target_arch = "arm", feature = "dcache")))]
+fn maintain(_: usize, _: usize, _: usize) {}
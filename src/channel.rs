@@ -9,8 +9,9 @@
 
 use crate::{
     element::Element,
+    peripheral::Window,
     ral::{self, dma, dmamux, tcd::BandwidthControl, Static},
-    Error,
+    Error, ValidationError,
 };
 
 impl<const CHANNELS: usize> super::Dma<CHANNELS> {
@@ -32,8 +33,33 @@ impl<const CHANNELS: usize> super::Dma<CHANNELS> {
             registers: self.controller,
             multiplexer: self.multiplexer,
             waker: &self.wakers[index],
+            cancel: &self.cancels[index],
+            error_reported: &self.errors_reported[index],
+            cache_maintenance: false,
         }
     }
+
+    /// Enable minor loop mapping for the DMA controller
+    ///
+    /// Minor loop mapping is a setting shared by all channels. It's required for
+    /// [`Channel::set_minor_loop_offset`], and for the [`transform`](crate::transform)
+    /// module. Once enabled, a channel's minor loop moves at most 2^30 bytes, or
+    /// 1023 bytes if it applies a minor loop offset.
+    ///
+    /// Enable mapping before you start any transfers. It stays enabled.
+    pub fn enable_minor_loop_mapping(&self) {
+        critical_section::with(|_| {
+            let cr = self.controller.CR.read();
+            self.controller.CR.write(cr | dma::RegisterBlock::EMLM);
+        });
+    }
+
+    /// Returns `true` if minor loop mapping is enabled
+    ///
+    /// See [`enable_minor_loop_mapping`](Self::enable_minor_loop_mapping).
+    pub fn is_minor_loop_mapping_enabled(&self) -> bool {
+        self.controller.CR.read() & dma::RegisterBlock::EMLM != 0
+    }
 }
 
 /// A DMA channel
@@ -52,6 +78,12 @@ pub struct Channel {
     multiplexer: Static<dmamux::RegisterBlock>,
     /// This channel's waker.
     pub(crate) waker: &'static super::SharedWaker,
+    /// Set when someone requests a cancellation.
+    pub(crate) cancel: &'static core::sync::atomic::AtomicBool,
+    /// Set once the error is reported to the channel's callback.
+    pub(crate) error_reported: &'static core::sync::atomic::AtomicBool,
+    /// Should the safe futures maintain the data cache?
+    cache_maintenance: bool,
 }
 
 impl Channel {
@@ -90,6 +122,29 @@ impl Channel {
         ral::modify_reg!(crate::ral::tcd, tcd, CSR, BWC: raw);
     }
 
+    /// Enable or disable data cache maintenance in the DMA futures
+    ///
+    /// When enabled, the safe DMA futures, like [`memcpy`](crate::memcpy::memcpy)
+    /// and [`read`](crate::peripheral::read), clean source buffers before the
+    /// transfer, and invalidate destination buffers when the transfer ends.
+    /// See the [`cache`](crate::cache) module for more information, including
+    /// buffer alignment. Cache maintenance is disabled by default.
+    ///
+    /// Enable cache maintenance if your buffers are in cacheable memory.
+    pub fn set_cache_maintenance(&mut self, enable: bool) {
+        self.cache_maintenance = enable;
+    }
+
+    /// Returns `true` if the DMA futures maintain the data cache for this channel
+    pub fn cache_maintenance(&self) -> bool {
+        self.cache_maintenance
+    }
+
+    /// Returns `true` if the DMA controller maps minor loops
+    pub(crate) fn minor_loop_mapping(&self) -> bool {
+        self.registers.CR.read() & dma::RegisterBlock::EMLM != 0
+    }
+
     /// Reset the transfer control descriptor owned by the DMA channel
     ///
     /// `reset` should be called during channel initialization to put the
@@ -244,6 +299,63 @@ impl Channel {
         ral::write_reg!(crate::ral::tcd, tcd, NBYTES, nbytes);
     }
 
+    /// Apply a signed `offset`, in bytes, to the source and / or destination
+    /// address after each minor loop
+    ///
+    /// Minor loop offsets let one major loop walk a two-dimensional pattern,
+    /// like a matrix column. Call this after [`set_minor_loop_bytes`](Self::set_minor_loop_bytes),
+    /// which clears the offset. Apply the offset to neither address to clear it.
+    ///
+    /// Minor loop offsets require minor loop mapping, a setting shared by all
+    /// channels. Enable it with [`Dma::enable_minor_loop_mapping`](crate::Dma::enable_minor_loop_mapping).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset applies to an address, and
+    ///
+    /// - minor loop mapping is not enabled
+    /// - the minor loop bytes exceed 1023
+    /// - the offset does not fit in 20 signed bits
+    ///
+    /// # Safety
+    ///
+    /// This may allow the DMA engine to read beyond the source, or write beyond
+    /// the destination. Caller must ensure that the offset is valid for the transfer.
+    pub unsafe fn set_minor_loop_offset(&self, source: bool, destination: bool, offset: i32) {
+        use crate::ral::tcd::NBYTES;
+
+        let tcd = self.tcd();
+        let nbytes = tcd.NBYTES.read();
+        let nbytes = if nbytes & (NBYTES::SMLOE::mask | NBYTES::DMLOE::mask) != 0 {
+            nbytes & NBYTES::NBYTES::mask
+        } else {
+            nbytes & NBYTES::NBYTES::mask_mloffno
+        };
+        if !source && !destination {
+            tcd.NBYTES.write(nbytes);
+            return;
+        }
+
+        assert!(
+            self.minor_loop_mapping(),
+            "DMA minor loop mapping is not enabled"
+        );
+        assert!(
+            nbytes <= NBYTES::NBYTES::mask,
+            "DMA minor loop bytes exceed 1023 with a minor loop offset"
+        );
+        assert!(
+            (-(1 << 19)..1 << 19).contains(&offset),
+            "DMA minor loop offset does not fit in 20 bits"
+        );
+        tcd.NBYTES.write(
+            ((source as u32) << NBYTES::SMLOE::offset)
+                | ((destination as u32) << NBYTES::DMLOE::offset)
+                | (((offset as u32) << NBYTES::MLOFF::offset) & NBYTES::MLOFF::mask)
+                | nbytes,
+        );
+    }
+
     /// Tells the DMA channel how many transfer iterations to perform
     ///
     /// A 'transfer iteration' is a read from a source, and a write to a destination, with
@@ -265,6 +377,23 @@ impl Channel {
         ral::modify_reg!(crate::ral::tcd, tcd, BITER, BITER: iterations);
     }
 
+    /// Reprogram the channel for one last minor loop of `nbytes`
+    ///
+    /// Use this once a transfer completes, to continue the transfer
+    /// with a shorter minor loop.
+    ///
+    /// # Safety
+    ///
+    /// The channel must be disabled, and the minor loop must be valid for
+    /// the transfer's memory.
+    pub(crate) unsafe fn set_final_minor_loop(&mut self, nbytes: u32) {
+        // The writes clear ELINK, just like set_transfer_iterations.
+        let tcd = self.tcd();
+        ral::write_reg!(crate::ral::tcd, tcd, NBYTES, nbytes);
+        ral::write_reg!(crate::ral::tcd, tcd, CITER, 1);
+        ral::write_reg!(crate::ral::tcd, tcd, BITER, 1);
+    }
+
     /// Returns the beginning transfer iterations setting for the channel.
     ///
     /// This reflects the last call to `set_transfer_iterations`.
@@ -273,6 +402,73 @@ impl Channel {
         ral::read_reg!(crate::ral::tcd, tcd, BITER, BITER)
     }
 
+    /// Returns the current transfer iterations for the channel.
+    ///
+    /// The DMA engine decrements this count after each minor loop. The count
+    /// reloads from the beginning transfer iterations once the major loop
+    /// completes. Compare the two to learn how many minor loops have run
+    /// in an incomplete transfer.
+    pub fn current_transfer_iterations(&self) -> u16 {
+        let tcd = self.tcd();
+        ral::read_reg!(crate::ral::tcd, tcd, CITER, CITER)
+    }
+
+    /// Returns a copy of the channel's transfer control descriptor
+    pub(crate) fn descriptor(&self) -> Descriptor {
+        let tcd = self.tcd();
+        Descriptor {
+            saddr: tcd.SADDR.read(),
+            soff: tcd.SOFF.read(),
+            dattr: tcd.DATTR.read(),
+            sattr: tcd.SATTR.read(),
+            nbytes: tcd.NBYTES.read(),
+            slast: tcd.SLAST.read(),
+            daddr: tcd.DADDR.read(),
+            doff: tcd.DOFF.read(),
+            citer: tcd.CITER.read(),
+            dlast_sga: tcd.DLAST_SGA.read(),
+            csr: tcd.CSR.read(),
+            biter: tcd.BITER.read(),
+        }
+    }
+
+    /// Load `descriptor` into the channel's transfer control descriptor
+    ///
+    /// # Safety
+    ///
+    /// The descriptor must describe a valid transfer. If the descriptor
+    /// enables scatter / gather, the chain of descriptors must stay valid
+    /// for the transfer.
+    pub(crate) unsafe fn set_descriptor(&mut self, descriptor: &Descriptor) {
+        // The channel ignores ESG while DONE is set.
+        self.clear_complete();
+        let tcd = self.tcd();
+        tcd.SADDR.write(descriptor.saddr);
+        tcd.SOFF.write(descriptor.soff);
+        tcd.DATTR.write(descriptor.dattr);
+        tcd.SATTR.write(descriptor.sattr);
+        tcd.NBYTES.write(descriptor.nbytes);
+        tcd.SLAST.write(descriptor.slast);
+        tcd.DADDR.write(descriptor.daddr);
+        tcd.DOFF.write(descriptor.doff);
+        tcd.CITER.write(descriptor.citer);
+        tcd.DLAST_SGA.write(descriptor.dlast_sga);
+        tcd.BITER.write(descriptor.biter);
+        tcd.CSR.write(descriptor.csr);
+    }
+
+    /// Stop a scatter / gather chain from loading its next descriptor
+    ///
+    /// Call this once the channel is disabled. It waits for the active
+    /// minor loop to finish.
+    pub(crate) fn end_scatter_gather(&self) {
+        let tcd = self.tcd();
+        if ral::read_reg!(crate::ral::tcd, tcd, CSR, ESG == 1) {
+            while self.is_active() {}
+            ral::modify_reg!(crate::ral::tcd, tcd, CSR, ESG: 0);
+        }
+    }
+
     /// Set the DMAMUX channel configuration
     ///
     /// See the [`Configuration`] documentation for more information.
@@ -305,11 +501,52 @@ impl Channel {
         }
     }
 
+    /// Returns the DMAMUX channel configuration
+    ///
+    /// This reads the configuration back from the DMAMUX. See
+    /// [`set_channel_configuration`](Channel::set_channel_configuration).
+    pub fn channel_configuration(&self) -> Configuration {
+        let chcfg = self.multiplexer.chcfg[self.index].read();
+        if chcfg & dmamux::RegisterBlock::ENBL == 0 {
+            Configuration::Off
+        } else if chcfg & dmamux::RegisterBlock::A_ON != 0 {
+            Configuration::AlwaysOn
+        } else {
+            Configuration::Enable {
+                source: chcfg & dmamux::RegisterBlock::SOURCE,
+                periodic: chcfg & dmamux::RegisterBlock::TRIG != 0,
+            }
+        }
+    }
+
     /// Returns `true` if the DMA channel is receiving a service signal from hardware
     pub fn is_hardware_signaling(&self) -> bool {
         self.registers.HRS.read() & (1 << self.index) != 0
     }
 
+    /// Enable or disable asynchronous DMA requests in stop mode
+    ///
+    /// When enabled, the channel's peripheral can request DMA service while the
+    /// chip is in stop mode. The request wakes the DMA controller, which performs
+    /// the transfer. Use this to let a peripheral, like a UART, wake the chip
+    /// by DMA. Asynchronous requests are disabled by default.
+    pub fn set_async_request(&mut self, enable: bool) {
+        // EARS is shared by all channels, and there are no set / clear
+        // registers for EARS.
+        critical_section::with(|_| {
+            let ears = self.registers.EARS.read();
+            let mask = 1 << self.index;
+            self.registers
+                .EARS
+                .write(if enable { ears | mask } else { ears & !mask });
+        });
+    }
+
+    /// Returns `true` if asynchronous DMA requests in stop mode are enabled
+    pub fn is_async_request(&self) -> bool {
+        self.registers.EARS.read() & (1 << self.index) != 0
+    }
+
     /// Disable the DMA channel, preventing any DMA transfers
     pub fn disable(&self) {
         // Immutable write OK. No other methods directly modify ERQ.
@@ -344,6 +581,16 @@ impl Channel {
         ral::modify_reg!(crate::ral::tcd, tcd, CSR, INTMAJOR: intr as u16);
     }
 
+    /// Enable or disable interrupt generation when the transfer is half complete
+    ///
+    /// The interrupt fires once the channel completes half of its major loop
+    /// iterations. Use this with a [`Callback`](crate::Callback) to service
+    /// double buffers. You're responsible for registering your interrupt handler.
+    pub fn set_interrupt_on_half(&mut self, intr: bool) {
+        let tcd = self.tcd();
+        ral::modify_reg!(crate::ral::tcd, tcd, CSR, INTHALF: intr as u16);
+    }
+
     /// Indicates if the DMA transfer has completed
     pub fn is_complete(&self) -> bool {
         let tcd = self.tcd();
@@ -368,6 +615,8 @@ impl Channel {
         // Immutable write OK. CERR affects a bit in ERR, which is
         // not written to elsewhere.
         self.registers.CERR.write(self.index as u8);
+        self.error_reported
+            .store(false, core::sync::atomic::Ordering::Release);
     }
 
     /// Indicates if this DMA channel is actively transferring data
@@ -389,6 +638,40 @@ impl Channel {
         Error::new(self.registers.ES.read())
     }
 
+    /// Returns the channel's state
+    ///
+    /// The state includes the channel's status flags, its DMAMUX configuration,
+    /// and every field of its transfer control descriptor, decoded. Use it to
+    /// debug a channel that isn't behaving. Compare the state against your expected
+    /// setup, or log it with `Display`.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::channel::Configuration;
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// let channel = // Acquire your channel...
+    ///     # unsafe { DMA.channel(7) };
+    /// // Prepare a transfer...
+    /// let state = channel.state();
+    /// assert_eq!(state.configuration, Configuration::AlwaysOn);
+    /// assert_eq!(state.source.size, 4);
+    /// assert!(state.control.disable_on_completion);
+    /// ```
+    pub fn state(&self) -> State {
+        State::decode(self, self.minor_loop_mapping(), &self.descriptor())
+    }
+
+    /// Check the channel's transfer control descriptor before a transfer
+    ///
+    /// Returns the first field that would produce a configuration error once the
+    /// channel is enabled. See [`State::validate`] for the checks.
+    ///
+    /// [`Transfer`](crate::Transfer) doesn't validate the channel. Call `validate`
+    /// after you prepare a transfer, and before you enable the channel.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.state().validate()
+    }
+
     /// Start a DMA transfer
     ///
     /// `start()` should be used to request service from the DMA controller. It's
@@ -410,8 +693,365 @@ impl Channel {
 // them being (mutably) shared.
 unsafe impl Send for Channel {}
 
+/// A transfer control descriptor in memory
+///
+/// Vectored transfers, like [`write_vectored`](crate::peripheral::write_vectored),
+/// describe each buffer with a `Descriptor`. The DMA channel loads the next
+/// descriptor when it finishes a buffer. You provide the descriptors, and
+/// the transfer borrows them until it's dropped.
+///
+/// Descriptors are aligned to 32 bytes, as required by the DMA controller.
+/// Place them in memory that the DMA controller can read.
+///
+/// ```
+/// use imxrt_dma::channel::Descriptor;
+///
+/// let descriptors = [Descriptor::new(); 3];
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C, align(32))]
+pub struct Descriptor {
+    saddr: u32,
+    soff: i16,
+    dattr: u8,
+    sattr: u8,
+    nbytes: u32,
+    slast: i32,
+    daddr: u32,
+    doff: i16,
+    citer: u16,
+    dlast_sga: i32,
+    csr: u16,
+    biter: u16,
+}
+
+const _STATIC_ASSERT_DESCRIPTOR_32_BYTES: [u32; 1] =
+    [0; (32 == core::mem::size_of::<Descriptor>()) as usize];
+
+impl Descriptor {
+    /// Create an empty descriptor
+    pub const fn new() -> Self {
+        Descriptor {
+            saddr: 0,
+            soff: 0,
+            dattr: 0,
+            sattr: 0,
+            nbytes: 0,
+            slast: 0,
+            daddr: 0,
+            doff: 0,
+            citer: 0,
+            dlast_sga: 0,
+            csr: 0,
+            biter: 0,
+        }
+    }
+
+    /// Set the source address, and the source's last address adjustment
+    pub(crate) fn set_source<E: Element>(&mut self, source: *const E, len: usize) {
+        self.saddr = source as u32;
+        self.slast = ((len * core::mem::size_of::<E>()) as i32).wrapping_neg();
+    }
+
+    /// Set the destination address, and the destination's last address adjustment
+    pub(crate) fn set_destination<E: Element>(&mut self, destination: *const E, len: usize) {
+        self.daddr = destination as u32;
+        self.dlast_sga = ((len * core::mem::size_of::<E>()) as i32).wrapping_neg();
+    }
+
+    /// Set the number of bytes per minor loop
+    pub(crate) fn set_minor_loop_bytes(&mut self, nbytes: u32) {
+        self.nbytes = nbytes;
+    }
+
+    /// Set the number of major loop iterations
+    pub(crate) fn set_transfer_iterations(&mut self, iterations: u16) {
+        self.citer = iterations;
+        self.biter = iterations;
+    }
+}
+
+/// A DMA channel's state
+///
+/// Use [`Channel::state`] to read the state. It decodes every field of the
+/// channel's transfer control descriptor (TCD). `Display` shows every field on
+/// one line, using the reference manual's field names. With the `defmt` feature,
+/// the state also implements `defmt::Format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub struct State {
+    /// The DMA channel number
+    pub channel: usize,
+    /// The channel is enabled (`ERQ`)
+    pub enabled: bool,
+    /// The channel is transferring data (`CSR[ACTIVE]`)
+    pub active: bool,
+    /// The channel completed its major loop (`CSR[DONE]`)
+    pub complete: bool,
+    /// The channel is in error (`ERR`)
+    pub error: bool,
+    /// The channel's interrupt flag is set (`INT`)
+    pub interrupt: bool,
+    /// The channel is receiving a service signal from hardware (`HRS`)
+    pub hardware_signaling: bool,
+    /// The DMAMUX channel configuration
+    pub configuration: Configuration,
+    /// The source side of the transfer
+    pub source: Endpoint,
+    /// The destination side of the transfer
+    ///
+    /// If [`scatter_gather`](Control::scatter_gather) is set, the destination's
+    /// `last_adjustment` is the address of the next TCD.
+    pub destination: Endpoint,
+    /// The number of bytes transferred per minor loop (`NBYTES`)
+    pub minor_loop_bytes: u32,
+    /// The signed offset, in bytes, applied after each minor loop (`MLOFF`)
+    ///
+    /// The offset applies to the source and / or destination, depending on
+    /// each endpoint's [`minor_loop_offset`](Endpoint::minor_loop_offset).
+    /// Zero if neither endpoint applies the offset.
+    pub minor_loop_offset: i32,
+    /// The current major loop iteration count (`CITER`)
+    pub current_iterations: u16,
+    /// The beginning major loop iteration count (`BITER`)
+    pub beginning_iterations: u16,
+    /// The control and status flags (`CSR`)
+    pub control: Control,
+}
+
+/// One side of a DMA transfer, decoded from a TCD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub struct Endpoint {
+    /// The address (`SADDR`, `DADDR`)
+    pub address: u32,
+    /// The signed offset, in bytes, applied after each element (`SOFF`, `DOFF`)
+    pub offset: i16,
+    /// The element size, in bytes (`SSIZE`, `DSIZE`)
+    ///
+    /// Zero if the TCD holds a reserved size.
+    pub size: u8,
+    /// The address modulo (`SMOD`, `DMOD`)
+    pub modulo: u8,
+    /// The signed adjustment, in bytes, applied after the major loop (`SLAST`, `DLAST_SGA`)
+    pub last_adjustment: i32,
+    /// The minor loop offset applies to this address (`SMLOE`, `DMLOE`)
+    pub minor_loop_offset: bool,
+}
+
+/// Control and status flags, decoded from a TCD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub struct Control {
+    /// Software requested service (`START`)
+    pub start: bool,
+    /// Interrupt when the major loop completes (`INTMAJOR`)
+    pub interrupt_on_completion: bool,
+    /// Interrupt when the major loop is half complete (`INTHALF`)
+    pub interrupt_on_half: bool,
+    /// Disable the hardware request when the major loop completes (`DREQ`)
+    pub disable_on_completion: bool,
+    /// Scatter / gather processing is enabled (`ESG`)
+    pub scatter_gather: bool,
+    /// Channel linking on major loop completion is enabled (`MAJORELINK`)
+    pub major_link: bool,
+    /// The channel is transferring data (`ACTIVE`)
+    pub active: bool,
+    /// The major loop completed (`DONE`)
+    pub done: bool,
+    /// The bandwidth control (`BWC`)
+    pub bandwidth_control: Option<BandwidthControl>,
+}
+
+impl State {
+    fn decode(chan: &Channel, minor_loop_mapping: bool, tcd: &Descriptor) -> Self {
+        use crate::ral::tcd::{ATTR, CSR, NBYTES};
+        let endpoint = |address, offset, attr: u8, last_adjustment, minor_loop_offset| {
+            let size = (attr & ATTR::SIZE::mask) >> ATTR::SIZE::offset;
+            Endpoint {
+                address,
+                offset,
+                // 4 is reserved; 5 is a 32-byte burst.
+                size: match size {
+                    0..=3 | 5 => 1 << size,
+                    _ => 0,
+                },
+                modulo: (attr & ATTR::MOD::mask) >> ATTR::MOD::offset,
+                last_adjustment,
+                minor_loop_offset,
+            }
+        };
+        let csr = |mask: u16| tcd.csr & mask != 0;
+        // With minor loop channel linking (ELINK, bit 15), the count is 9 bits.
+        let iterations = |iter: u16| {
+            if iter & 0x8000 != 0 {
+                iter & 0x1FF
+            } else {
+                iter & crate::ral::tcd::CITER::CITER::mask
+            }
+        };
+
+        let smloe = minor_loop_mapping && tcd.nbytes & NBYTES::SMLOE::mask != 0;
+        let dmloe = minor_loop_mapping && tcd.nbytes & NBYTES::DMLOE::mask != 0;
+        let (minor_loop_bytes, minor_loop_offset) = if smloe || dmloe {
+            // Shift the 20-bit field to the top of the word to sign extend it.
+            let mloff = ((tcd.nbytes & NBYTES::MLOFF::mask) << 2) as i32 >> 12;
+            (tcd.nbytes & NBYTES::NBYTES::mask, mloff)
+        } else if minor_loop_mapping {
+            (tcd.nbytes & NBYTES::NBYTES::mask_mloffno, 0)
+        } else {
+            (tcd.nbytes, 0)
+        };
+
+        State {
+            channel: chan.index,
+            enabled: chan.is_enabled(),
+            active: chan.is_active(),
+            complete: chan.is_complete(),
+            error: chan.is_error(),
+            interrupt: chan.is_interrupt(),
+            hardware_signaling: chan.is_hardware_signaling(),
+            configuration: chan.channel_configuration(),
+            source: endpoint(tcd.saddr, tcd.soff, tcd.sattr, tcd.slast, smloe),
+            destination: endpoint(tcd.daddr, tcd.doff, tcd.dattr, tcd.dlast_sga, dmloe),
+            minor_loop_bytes,
+            minor_loop_offset,
+            current_iterations: iterations(tcd.citer),
+            beginning_iterations: iterations(tcd.biter),
+            control: Control {
+                start: csr(CSR::START::mask),
+                interrupt_on_completion: csr(CSR::INTMAJOR::mask),
+                interrupt_on_half: csr(CSR::INTHALF::mask),
+                disable_on_completion: csr(CSR::DREQ::mask),
+                scatter_gather: csr(CSR::ESG::mask),
+                major_link: csr(CSR::MAJORELINK::mask),
+                active: csr(CSR::ACTIVE::mask),
+                done: csr(CSR::DONE::mask),
+                bandwidth_control: BandwidthControl::from_raw(
+                    (tcd.csr & CSR::BWC::mask) >> CSR::BWC::offset,
+                ),
+            },
+        }
+    }
+
+    /// Check the transfer control descriptor for configuration errors
+    ///
+    /// Checks that
+    ///
+    /// - the source and destination sizes are valid.
+    /// - the addresses are aligned to their sizes.
+    /// - the offsets are multiples of their sizes.
+    /// - the addresses are aligned to their modulo ranges, and the ranges hold
+    ///   at least one element.
+    /// - the minor loop bytes are non-zero, and a multiple of both sizes.
+    /// - the current iterations are non-zero, and equal to the beginning iterations.
+    /// - the scatter / gather address is aligned to 32 bytes, if scatter / gather
+    ///   is enabled.
+    ///
+    /// The checks assume that the transfer hasn't started.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        fn endpoint(
+            endpoint: &Endpoint,
+            [size, address, offset, modulo]: [ValidationError; 4],
+        ) -> Result<(), ValidationError> {
+            let bytes = endpoint.size as u32;
+            if bytes == 0 {
+                return Err(size);
+            }
+            if !endpoint.address.is_multiple_of(bytes) {
+                return Err(address);
+            }
+            if endpoint.offset as i32 % bytes as i32 != 0 {
+                return Err(offset);
+            }
+            if endpoint.modulo != 0 {
+                let range = 1u32 << endpoint.modulo;
+                if range < bytes || !endpoint.address.is_multiple_of(range) {
+                    return Err(modulo);
+                }
+            }
+            Ok(())
+        }
+
+        endpoint(
+            &self.source,
+            [
+                ValidationError::SourceSize,
+                ValidationError::SourceAddress,
+                ValidationError::SourceOffset,
+                ValidationError::SourceModulo,
+            ],
+        )?;
+        endpoint(
+            &self.destination,
+            [
+                ValidationError::DestinationSize,
+                ValidationError::DestinationAddress,
+                ValidationError::DestinationOffset,
+                ValidationError::DestinationModulo,
+            ],
+        )?;
+
+        let widest = self.source.size.max(self.destination.size) as u32;
+        if self.minor_loop_bytes == 0 || !self.minor_loop_bytes.is_multiple_of(widest) {
+            return Err(ValidationError::MinorLoopBytes);
+        }
+        if self.current_iterations == 0 || self.current_iterations != self.beginning_iterations {
+            return Err(ValidationError::Iterations);
+        }
+        if self.control.scatter_gather && self.destination.last_adjustment % 32 != 0 {
+            return Err(ValidationError::ScatterGather);
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for State {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (src, dst, ctl) = (&self.source, &self.destination, &self.control);
+        write!(f,
+            "DMA{channel}: ERQ {erq} ERR {err} INT {int} HRS {hrs} CHCFG {configuration:?} SADDR {saddr:#010X} SOFF {soff} SSIZE {ssize} SMOD {smod} SLAST {slast} DADDR {daddr:#010X} DOFF {doff} DSIZE {dsize} DMOD {dmod} DLAST_SGA {dlast} NBYTES {nbytes} SMLOE {smloe} DMLOE {dmloe} MLOFF {mloff} CITER {citer} BITER {biter} CSR: START {start} INTMAJOR {intmajor} INTHALF {inthalf} DREQ {dreq} ESG {esg} MAJORELINK {majorelink} ACTIVE {active} DONE {done} BWC {bwc}",
+            channel = self.channel,
+            erq = self.enabled as u32,
+            err = self.error as u32,
+            int = self.interrupt as u32,
+            hrs = self.hardware_signaling as u32,
+            configuration = self.configuration,
+            saddr = src.address,
+            soff = src.offset,
+            ssize = src.size,
+            smod = src.modulo,
+            slast = src.last_adjustment,
+            daddr = dst.address,
+            doff = dst.offset,
+            dsize = dst.size,
+            dmod = dst.modulo,
+            dlast = dst.last_adjustment,
+            nbytes = self.minor_loop_bytes,
+            smloe = src.minor_loop_offset as u32,
+            dmloe = dst.minor_loop_offset as u32,
+            mloff = self.minor_loop_offset,
+            citer = self.current_iterations,
+            biter = self.beginning_iterations,
+            start = ctl.start as u32,
+            intmajor = ctl.interrupt_on_completion as u32,
+            inthalf = ctl.interrupt_on_half as u32,
+            dreq = ctl.disable_on_completion as u32,
+            esg = ctl.scatter_gather as u32,
+            majorelink = ctl.major_link as u32,
+            active = ctl.active as u32,
+            done = ctl.done as u32,
+            bwc = BandwidthControl::raw(ctl.bandwidth_control),
+        )
+    }
+}
+
 /// DMAMUX channel configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum Configuration {
     /// The DMAMUX channel is disabled
@@ -491,6 +1131,73 @@ pub unsafe fn set_destination_hardware<E: Element>(
     chan.set_destination_last_address_adjustment(0);
 }
 
+/// Set a window of hardware registers as the source for a DMA transfer
+///
+/// `hardware_source` points at the first register in the window. Each minor
+/// loop reads every register in the window, then the source address wraps back
+/// to `hardware_source`. Use [`Window::minor_loop_bytes`] to size the minor loop.
+///
+/// # Safety
+///
+/// Caller must ensure that the whole window is valid for the lifetime of the transfer,
+/// and valid for all subsequent transfers performed by this DMA channel with this address.
+///
+/// # Panics
+///
+/// Panics if the window's width is not the size of `E`, or if `hardware_source`
+/// is not aligned to the window's modulo range.
+pub unsafe fn set_source_hardware_window<E: Element>(
+    chan: &mut Channel,
+    hardware_source: *const E,
+    window: &Window,
+) {
+    window_asserts(hardware_source, window);
+    chan.set_source_address(hardware_source);
+    chan.set_source_offset(window.stride());
+    chan.set_source_attributes::<E>(window.modulo());
+    chan.set_source_last_address_adjustment(0);
+}
+
+/// Set a window of hardware registers as the destination for a DMA transfer
+///
+/// `hardware_destination` points at the first register in the window. Each minor
+/// loop writes every register in the window, then the destination address wraps
+/// back to `hardware_destination`. Use [`Window::minor_loop_bytes`] to size the
+/// minor loop.
+///
+/// # Safety
+///
+/// Caller must ensure that the whole window is valid for the lifetime of the transfer,
+/// and valid for all subsequent transfers performed by this DMA channel with this address.
+///
+/// # Panics
+///
+/// Panics if the window's width is not the size of `E`, or if `hardware_destination`
+/// is not aligned to the window's modulo range.
+pub unsafe fn set_destination_hardware_window<E: Element>(
+    chan: &mut Channel,
+    hardware_destination: *const E,
+    window: &Window,
+) {
+    window_asserts(hardware_destination, window);
+    chan.set_destination_address(hardware_destination);
+    chan.set_destination_offset(window.stride());
+    chan.set_destination_attributes::<E>(window.modulo());
+    chan.set_destination_last_address_adjustment(0);
+}
+
+/// Assert properties about a register window
+fn window_asserts<E>(base: *const E, window: &Window) {
+    assert!(
+        window.width() as usize == core::mem::size_of::<E>(),
+        "DMA register window width does not match the element"
+    );
+    assert!(
+        (base as usize).is_multiple_of(1 << window.modulo()),
+        "DMA register window is not aligned to its modulo range"
+    );
+}
+
 /// Set a linear buffer as the source for a DMA transfer
 ///
 /// When the transfer completes, the DMA channel will point at the
@@ -535,7 +1242,7 @@ fn circular_buffer_asserts<E>(buffer: &[E]) {
     let start = buffer.as_ptr();
     let size = core::mem::size_of_val(buffer);
     assert!(
-        (start as usize) % size == 0,
+        (start as usize).is_multiple_of(size),
         "DMA circular buffer is not properly aligned"
     );
 }
@@ -599,3 +1306,56 @@ pub unsafe fn set_destination_circular_buffer<E: Element>(
     chan.set_destination_attributes::<E>(modulo as u8);
     chan.set_destination_last_address_adjustment(0);
 }
+
+/// Link `descriptors` into a scatter / gather chain, and load the first
+/// descriptor into the channel
+///
+/// The channel runs each descriptor in turn. Only the last descriptor keeps the
+/// channel's completion settings, like the completion interrupt, and 'disable on
+/// completion.' If `start` is set, each loaded descriptor starts itself, which
+/// suits software-started transfers.
+///
+/// If the channel [maintains the data cache](Channel::set_cache_maintenance), this
+/// cleans the descriptors.
+///
+/// # Safety
+///
+/// Each descriptor must describe a valid transfer. The caller must keep the
+/// descriptors, and the memory they describe, valid for the lifetime of the transfer.
+///
+/// # Panics
+///
+/// Panics if `descriptors` is empty.
+pub(crate) unsafe fn set_scatter_gather(
+    chan: &mut Channel,
+    descriptors: &mut [Descriptor],
+    start: bool,
+) {
+    use crate::ral::tcd::CSR;
+    assert!(
+        !descriptors.is_empty(),
+        "DMA scatter / gather chain is empty"
+    );
+
+    let base = descriptors.as_ptr() as u32;
+    let count = descriptors.len();
+    for (idx, descriptor) in descriptors.iter_mut().enumerate() {
+        descriptor.csr &= !(CSR::START::mask | CSR::ACTIVE::mask | CSR::DONE::mask);
+        if idx + 1 < count {
+            descriptor.csr &= !(CSR::INTMAJOR::mask | CSR::INTHALF::mask | CSR::DREQ::mask);
+            descriptor.csr |= CSR::ESG::mask;
+            descriptor.dlast_sga = base.wrapping_add(32 * (idx as u32 + 1)) as i32;
+        }
+        if start && idx > 0 {
+            descriptor.csr |= CSR::START::mask;
+        }
+    }
+
+    if chan.cache_maintenance() {
+        crate::cache::clean_range(
+            descriptors.as_ptr() as usize,
+            core::mem::size_of_val(descriptors),
+        );
+    }
+    chan.set_descriptor(&descriptors[0]);
+}
@@ -26,6 +26,18 @@ impl Element for u64 {
     const DATA_TRANSFER_ID: u8 = 3;
 }
 
+/// A 32-byte burst
+///
+/// Only used for wide memory copies. The DMA controller moves a
+/// burst with four 64-bit accesses.
+#[derive(Clone, Copy)]
+#[repr(C, align(32))]
+pub(crate) struct Burst([u64; 4]);
+
+impl Element for Burst {
+    const DATA_TRANSFER_ID: u8 = 5;
+}
+
 mod private {
     pub trait Sealed {}
 
@@ -33,4 +45,5 @@ mod private {
     impl Sealed for u16 {}
     impl Sealed for u32 {}
     impl Sealed for u64 {}
+    impl Sealed for super::Burst {}
 }
@@ -8,37 +8,75 @@ use core::fmt::{self, Debug, Display};
 /// error status register at the point of an error. The
 /// wrapper implements both `Debug` and `Display`. Format
 /// the error to see a summary of the error bits.
+///
+/// If software cancelled the transfer with [`Dma::cancel`](crate::Dma::cancel),
+/// there's no error status. See [`is_software_cancel`](Error::is_software_cancel).
 #[derive(Clone, Copy)]
 pub struct Error {
-    /// The raw error status
-    es: u32,
+    status: Status,
+}
+
+/// The source of an [`Error`]
+#[derive(Clone, Copy)]
+enum Status {
+    /// A copy of the error status register
+    Hardware(u32),
+    /// Software cancelled the transfer on this channel
+    Cancelled(u8),
 }
 
 impl Error {
     #[inline(always)]
     pub(crate) const fn new(es: u32) -> Self {
-        Error { es }
+        Error {
+            status: Status::Hardware(es),
+        }
+    }
+    /// Describes a software cancellation of the transfer on `channel`
+    #[inline(always)]
+    pub(crate) const fn cancelled(channel: usize) -> Self {
+        Error {
+            status: Status::Cancelled(channel as u8),
+        }
     }
     /// Returns the raw error status value
+    ///
+    /// Returns zero if software cancelled the transfer.
     #[inline(always)]
     pub const fn raw(self) -> u32 {
-        self.es
+        match self.status {
+            Status::Hardware(es) => es,
+            Status::Cancelled(_) => 0,
+        }
     }
     #[inline(always)]
     const fn is_bit(self, bit: u32) -> bool {
-        (self.es >> bit) & 1 != 0
+        (self.raw() >> bit) & 1 != 0
+    }
+    /// Indicates that software cancelled the transfer
+    ///
+    /// The transfer was cancelled with [`Dma::cancel`](crate::Dma::cancel).
+    /// The error has no error status, and only [`is_cancelled`](Error::is_cancelled)
+    /// and [`channel_number`](Error::channel_number) describe the error.
+    #[inline(always)]
+    pub const fn is_software_cancel(self) -> bool {
+        matches!(self.status, Status::Cancelled(_))
     }
     /// Logical OR of all DMA channel error status bits
     ///
-    /// If you have an `Error` this should always be true.
+    /// If you have an `Error` from the hardware, this should always be true.
+    /// It's false if software cancelled the transfer.
     #[inline(always)]
     pub const fn is_valid(self) -> bool {
         self.is_bit(31)
     }
     /// Indicates if the transfer was cancelled
+    ///
+    /// This is also set when software cancels a transfer with
+    /// [`Dma::cancel`](crate::Dma::cancel).
     #[inline(always)]
     pub const fn is_cancelled(self) -> bool {
-        self.is_bit(16)
+        self.is_software_cancel() || self.is_bit(16)
     }
     /// Indicates a group priority error
     #[inline(always)]
@@ -53,7 +91,10 @@ impl Error {
     /// Indicates the channel number
     #[inline(always)]
     pub const fn channel_number(self) -> u32 {
-        (self.es >> 8) & 0x1F
+        match self.status {
+            Status::Hardware(es) => (es >> 8) & 0x1F,
+            Status::Cancelled(channel) => channel as u32,
+        }
     }
     /// Indicates a source address error
     #[inline(always)]
@@ -95,16 +136,185 @@ impl Error {
     pub const fn is_destination_bus(self) -> bool {
         self.is_bit(0)
     }
+    /// Indicates if `kind` is set in this error
+    #[inline(always)]
+    pub const fn is_kind(self, kind: ErrorKind) -> bool {
+        match kind {
+            ErrorKind::Cancelled => self.is_cancelled(),
+            _ => self.is_bit(kind.bit()),
+        }
+    }
+    /// Returns an iterator over all error kinds set in this error
+    ///
+    /// ```
+    /// use imxrt_dma::{Error, ErrorKind};
+    ///
+    /// fn log_error(err: Error) {
+    ///     for kind in err.kinds() {
+    ///         match kind {
+    ///             ErrorKind::SourceBus | ErrorKind::DestinationBus => { /* ... */ }
+    ///             _ => { /* ... */ }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn kinds(self) -> ErrorKinds {
+        ErrorKinds {
+            error: self,
+            next: 0,
+        }
+    }
+    /// Classifies the error
+    ///
+    /// If the error has kinds from more than one class, the most severe class
+    /// is returned. From most to least severe, that's
+    ///
+    /// 1. [`Configuration`](ErrorClass::Configuration)
+    /// 2. [`Priority`](ErrorClass::Priority)
+    /// 3. [`Bus`](ErrorClass::Bus)
+    /// 4. [`Cancellation`](ErrorClass::Cancellation)
+    ///
+    /// So a transfer that only saw bus errors is classified as `Bus`, and it may
+    /// be worth retrying. Returns `None` if no error kinds are set.
+    pub fn class(self) -> Option<ErrorClass> {
+        self.kinds().map(ErrorKind::class).min()
+    }
 }
 
+/// A DMA error kind
+///
+/// Each kind corresponds to an error bit in the DMA controller's error status
+/// register. Use [`Error::kinds`] to iterate over the kinds in an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The transfer was cancelled (`ECX`)
+    Cancelled,
+    /// Group priority error (`GPE`)
+    GroupPriority,
+    /// Channel priority error (`CPE`)
+    ChannelPriority,
+    /// Source address error (`SAE`)
+    SourceAddress,
+    /// Source offset error (`SOE`)
+    SourceOffset,
+    /// Destination address error (`DAE`)
+    DestinationAddress,
+    /// Destination offset error (`DOE`)
+    DestinationOffset,
+    /// Minor / major loop configuration error (`NCE`)
+    LoopConfiguration,
+    /// Scatter / gather configuration error (`SGE`)
+    ScatterGather,
+    /// Source bus error (`SBE`)
+    SourceBus,
+    /// Destination bus error (`DBE`)
+    DestinationBus,
+}
+
+impl ErrorKind {
+    /// All error kinds
+    pub const ALL: [ErrorKind; 11] = [
+        ErrorKind::Cancelled,
+        ErrorKind::GroupPriority,
+        ErrorKind::ChannelPriority,
+        ErrorKind::SourceAddress,
+        ErrorKind::SourceOffset,
+        ErrorKind::DestinationAddress,
+        ErrorKind::DestinationOffset,
+        ErrorKind::LoopConfiguration,
+        ErrorKind::ScatterGather,
+        ErrorKind::SourceBus,
+        ErrorKind::DestinationBus,
+    ];
+    /// The error kind's bit in the error status register
+    const fn bit(self) -> u32 {
+        match self {
+            ErrorKind::Cancelled => 16,
+            ErrorKind::GroupPriority => 15,
+            ErrorKind::ChannelPriority => 14,
+            ErrorKind::SourceAddress => 7,
+            ErrorKind::SourceOffset => 6,
+            ErrorKind::DestinationAddress => 5,
+            ErrorKind::DestinationOffset => 4,
+            ErrorKind::LoopConfiguration => 3,
+            ErrorKind::ScatterGather => 2,
+            ErrorKind::SourceBus => 1,
+            ErrorKind::DestinationBus => 0,
+        }
+    }
+    /// Returns the error kind's class
+    pub const fn class(self) -> ErrorClass {
+        match self {
+            ErrorKind::Cancelled => ErrorClass::Cancellation,
+            ErrorKind::GroupPriority | ErrorKind::ChannelPriority => ErrorClass::Priority,
+            ErrorKind::SourceBus | ErrorKind::DestinationBus => ErrorClass::Bus,
+            ErrorKind::SourceAddress
+            | ErrorKind::SourceOffset
+            | ErrorKind::DestinationAddress
+            | ErrorKind::DestinationOffset
+            | ErrorKind::LoopConfiguration
+            | ErrorKind::ScatterGather => ErrorClass::Configuration,
+        }
+    }
+}
+
+/// A class of DMA error kinds
+///
+/// The classes are ordered from most to least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ErrorClass {
+    /// The transfer control descriptor is invalid. This is a programming error.
+    Configuration,
+    /// The channel priorities are invalid. This is a programming error.
+    Priority,
+    /// The transfer caused a bus error. The memory or peripheral may be
+    /// unavailable, or the address may not be mapped.
+    Bus,
+    /// Software, or hardware, cancelled the transfer.
+    Cancellation,
+}
+
+/// An iterator over the [`ErrorKind`]s in an [`Error`]
+///
+/// Use [`Error::kinds`] to create this iterator.
+#[derive(Debug, Clone)]
+pub struct ErrorKinds {
+    error: Error,
+    next: usize,
+}
+
+impl Iterator for ErrorKinds {
+    type Item = ErrorKind;
+    fn next(&mut self) -> Option<ErrorKind> {
+        while let Some(&kind) = ErrorKind::ALL.get(self.next) {
+            self.next += 1;
+            if self.error.is_kind(kind) {
+                return Some(kind);
+            }
+        }
+        None
+    }
+}
+
+impl core::iter::FusedIterator for ErrorKinds {}
+
 impl Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "DMA_ES({:#010X})", self.es)
+        match self.status {
+            Status::Hardware(es) => write!(f, "DMA_ES({es:#010X})"),
+            Status::Cancelled(channel) => write!(f, "DMA_CANCELLED({channel})"),
+        }
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Status::Cancelled(channel) = self.status {
+            return write!(f, "DMA channel {channel} cancelled by software");
+        }
         write!(f,
              "DMA_ES: VLD {vld} ECX {ecx} GPE {gpe} CPE {cpe} ERRCHN {errchn} SAE {sae} SOE {soe} DAE {dae} DOE {doe} NCE {nce} SGE {sge} SBE {sbe} DBE {dbe}",
              vld = self.is_valid() as u32,
@@ -123,3 +333,89 @@ impl Display for Error {
          )
     }
 }
+
+/// A transfer control descriptor (TCD) field that's not valid
+///
+/// [`Channel::validate`](crate::channel::Channel::validate) checks a channel's
+/// TCD before a transfer, and returns a `ValidationError` that names the first
+/// invalid field. The hardware detects many of the same problems, but only
+/// after the channel is enabled.
+///
+/// The field names match the reference manual.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// `SSIZE` is a reserved value.
+    SourceSize,
+    /// `SADDR` is not aligned to `SSIZE`.
+    SourceAddress,
+    /// `SOFF` is not a multiple of `SSIZE`.
+    SourceOffset,
+    /// `SADDR` is not aligned to the `SMOD` range, or the range is smaller than `SSIZE`.
+    SourceModulo,
+    /// `DSIZE` is a reserved value.
+    DestinationSize,
+    /// `DADDR` is not aligned to `DSIZE`.
+    DestinationAddress,
+    /// `DOFF` is not a multiple of `DSIZE`.
+    DestinationOffset,
+    /// `DADDR` is not aligned to the `DMOD` range, or the range is smaller than `DSIZE`.
+    DestinationModulo,
+    /// `NBYTES` is zero, or it's not a multiple of `SSIZE` and `DSIZE`.
+    MinorLoopBytes,
+    /// `CITER` is zero, or it's not equal to `BITER`.
+    Iterations,
+    /// `DLAST_SGA` is not aligned to 32 bytes, and scatter / gather is enabled.
+    ScatterGather,
+}
+
+impl ValidationError {
+    /// Returns the name of the invalid TCD field
+    pub const fn field(self) -> &'static str {
+        match self {
+            ValidationError::SourceSize => "SSIZE",
+            ValidationError::SourceAddress => "SADDR",
+            ValidationError::SourceOffset => "SOFF",
+            ValidationError::SourceModulo => "SMOD",
+            ValidationError::DestinationSize => "DSIZE",
+            ValidationError::DestinationAddress => "DADDR",
+            ValidationError::DestinationOffset => "DOFF",
+            ValidationError::DestinationModulo => "DMOD",
+            ValidationError::MinorLoopBytes => "NBYTES",
+            ValidationError::Iterations => "CITER",
+            ValidationError::ScatterGather => "DLAST_SGA",
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid TCD field {}", self.field())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Error {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        if let Status::Cancelled(channel) = self.status {
+            return defmt::write!(f, "DMA channel {=u8} cancelled by software", channel);
+        }
+        defmt::write!(f,
+            "DMA_ES: VLD {=u32} ECX {=u32} GPE {=u32} CPE {=u32} ERRCHN {=u32} SAE {=u32} SOE {=u32} DAE {=u32} DOE {=u32} NCE {=u32} SGE {=u32} SBE {=u32} DBE {=u32}",
+            self.is_valid() as u32,
+            self.is_cancelled() as u32,
+            self.is_group_priority() as u32,
+            self.is_channel_priority() as u32,
+            self.channel_number(),
+            self.is_source_address() as u32,
+            self.is_source_offset() as u32,
+            self.is_destination_address() as u32,
+            self.is_destination_offset() as u32,
+            self.is_loop_configuration() as u32,
+            self.is_scatter_gather() as u32,
+            self.is_source_bus() as u32,
+            self.is_destination_bus() as u32,
+        )
+    }
+}
@@ -1,23 +1,27 @@
 //! DMA interrupt support
 
 use crate::{channel::Channel, Error};
+use atomic_waker::AtomicWaker;
 use core::{
-    cell::RefCell,
+    cell::Cell,
     future::Future,
     marker::PhantomPinned,
     pin::Pin,
-    sync::atomic,
+    sync::atomic::{self, AtomicBool},
     task::{Context, Poll, Waker},
 };
 
-use cortex_m::interrupt::{self, Mutex};
+use critical_section::Mutex;
 
 impl<const CHANNELS: usize> super::Dma<CHANNELS> {
     /// Handle a DMA interrupt
     ///
     /// Checks the interrupt status for the channel identified by `channel`.
     /// If the channel completed its transfer, `on_interrupt` wakes the channel's
-    /// waker.
+    /// waker. If the channel has a [`Callback`], `on_interrupt` calls it when the
+    /// channel's interrupt flag is set, or when the channel is in error. The
+    /// callback sees each error once, even if more than one interrupt handler
+    /// services the channel before the error is cleared.
     ///
     /// Consider calling `on_interrupt` in a DMA channel's interrupt handler:
     ///
@@ -49,25 +53,237 @@ impl<const CHANNELS: usize> super::Dma<CHANNELS> {
     #[inline(always)]
     pub unsafe fn on_interrupt(&'static self, channel: usize) {
         let channel = self.channel(channel);
-        if channel.is_interrupt() {
+        let is_interrupt = channel.is_interrupt();
+        if is_interrupt {
             channel.clear_interrupt();
         }
 
-        if channel.is_complete() | channel.is_error() {
-            interrupt::free(|cs| {
-                let waker = self.wakers[channel.channel()].borrow(cs);
-                let mut waker = waker.borrow_mut();
-                if let Some(waker) = waker.take() {
-                    waker.wake();
+        let callback =
+            critical_section::with(|cs| self.callbacks[channel.channel()].borrow(cs).get());
+        if let Some(callback) = callback {
+            if channel.is_error() {
+                if !channel.error_reported.swap(true, atomic::Ordering::AcqRel) {
+                    callback(channel.channel(), Status::Error(channel.error_status()));
                 }
-            });
+            } else if is_interrupt && channel.is_complete() {
+                callback(channel.channel(), Status::Complete);
+            } else if is_interrupt {
+                callback(channel.channel(), Status::HalfComplete);
+            }
+        }
+
+        if channel.is_complete() | channel.is_error() {
+            self.wakers[channel.channel()].wake();
+            self.quiesce[channel.channel()].wake();
+        }
+    }
+}
+
+impl<const CHANNELS: usize> super::Dma<CHANNELS> {
+    /// Handle a DMA interrupt for every channel that needs it
+    ///
+    /// Reads the controller's interrupt and error flags, then calls
+    /// [`on_interrupt`](Self::on_interrupt) for each channel with a flag set.
+    /// Use `on_interrupt_all` when you don't want to track which channels share
+    /// an interrupt vector. It's also a simple handler for the DMA error
+    /// interrupt. See [`bind_interrupts!`](crate::bind_interrupts) to generate
+    /// the interrupt handlers.
+    ///
+    /// ```
+    /// use imxrt_dma::Dma;
+    /// static DMA: Dma<32> = // Handle to DMA driver.
+    /// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// // #[cortex_m_rt::interrupt]
+    /// fn DMA_ERROR() {
+    ///     // Safety: all DMA channels are used by DMA transfer futures.
+    ///     unsafe { DMA.on_interrupt_all() };
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// See [`on_interrupt`](Self::on_interrupt). The requirements apply to every
+    /// channel that has an interrupt or error flag set.
+    pub unsafe fn on_interrupt_all(&'static self) {
+        self.on_interrupt_mask(u32::MAX);
+    }
+
+    /// Handle a DMA interrupt for every channel in `mask` that needs it
+    pub(crate) unsafe fn on_interrupt_mask(&'static self, mask: u32) {
+        let mut pending = (self.controller.INT.read() | self.controller.ERR.read()) & mask;
+        while pending != 0 {
+            let channel = pending.trailing_zeros() as usize;
+            pending &= pending - 1;
+            if channel < CHANNELS {
+                self.on_interrupt(channel);
+            }
         }
     }
 }
 
-pub(crate) type SharedWaker = Mutex<RefCell<Option<Waker>>>;
+impl<const CHANNELS: usize> super::Dma<CHANNELS> {
+    /// Cancel the transfer that's using `channel`
+    ///
+    /// The next time the transfer future is polled, it stops the channel and
+    /// resolves with an [`Error`] that [`is_cancelled`](Error::is_cancelled).
+    /// `cancel` wakes the transfer's waker, so you may call it from any execution
+    /// context, including an interrupt handler.
+    ///
+    /// If there's no transfer using the channel, `cancel` has no effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than or equal to the maximum number of channels.
+    pub fn cancel(&'static self, channel: usize) {
+        self.cancels[channel].store(true, atomic::Ordering::Release);
+        self.wakers[channel].wake();
+    }
+}
+
+impl<const CHANNELS: usize> super::Dma<CHANNELS> {
+    /// Set the callback for `channel`
+    ///
+    /// [`on_interrupt`](Self::on_interrupt) calls the callback from the channel's
+    /// interrupt handler. Use `None` to remove the callback. A callback is an
+    /// alternative to awaiting a transfer future, and you may use both.
+    ///
+    /// `on_interrupt` doesn't clear the channel's completion or error flags. If
+    /// there's no transfer future that clears the flags, the callback should
+    /// clear them with [`clear_complete`](crate::channel::Channel::clear_complete)
+    /// and [`clear_error`](crate::channel::Channel::clear_error).
+    ///
+    /// ```
+    /// use imxrt_dma::{Dma, Status};
+    /// static DMA: Dma<32> = // Handle to DMA driver.
+    /// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// fn on_audio_dma(channel: usize, status: Status) {
+    ///     match status {
+    ///         Status::HalfComplete => { /* Refill the first half */ }
+    ///         Status::Complete => { /* Refill the second half */ }
+    ///         Status::Error(err) => panic!("DMA{channel}: {err}"),
+    ///     }
+    /// }
+    ///
+    /// DMA.set_callback(7, Some(on_audio_dma));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than or equal to the maximum number of channels.
+    pub fn set_callback(&'static self, channel: usize, callback: Option<Callback>) {
+        critical_section::with(|cs| self.callbacks[channel].borrow(cs).set(callback));
+    }
+}
+
+/// Define the DMA interrupt handlers for an i.MX RT chip
+///
+/// `bind_interrupts!` generates one handler per DMA interrupt vector. Each handler
+/// calls [`on_interrupt`](crate::Dma::on_interrupt) for the channels that share
+/// the vector, so you don't need to remember which channels signal which vector.
+/// The `DMA_ERROR` handler calls [`on_interrupt_all`](crate::Dma::on_interrupt_all).
+/// A channel's error may reach both handlers, but its [`Callback`](crate::Callback)
+/// sees the error once.
+///
+/// Specify one of the supported chips, followed by your static [`Dma`](crate::Dma).
+/// The handlers have the vector names used by `imxrt-ral` and the `cortex-m-rt`
+/// device vector table.
+///
+/// | Chip                                                 | Handlers                                        |
+/// | ---------------------------------------------------- | ----------------------------------------------- |
+/// | `imxrt1010`                                          | `DMA0` through `DMA15`, `DMA_ERROR`             |
+/// | `imxrt1020`, `imxrt1050`, `imxrt1060`, `imxrt1064`, `imxrt1170` | `DMA0_DMA16` through `DMA15_DMA31`, `DMA_ERROR` |
+///
+/// To split the channels between cores, use a [`Partition`](crate::Partition) in place
+/// of the `Dma`. The handlers only service the partition's channels.
+///
+/// The `unsafe` keyword is required, since the handlers call `on_interrupt`.
+/// You must meet `on_interrupt`'s safety requirements for every channel on the chip.
+/// You're still responsible for unmasking the interrupts in the NVIC.
+///
+/// ```
+/// use imxrt_dma::Dma;
+/// static DMA: Dma<32> = // Handle to DMA driver.
+/// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// // Safety: all DMA channels are used by DMA transfer futures.
+/// imxrt_dma::bind_interrupts!(unsafe imxrt1060, DMA);
+/// ```
+#[macro_export]
+macro_rules! bind_interrupts {
+    (unsafe imxrt1010, $dma:path) => {
+        $crate::bind_interrupts!(@handlers $dma;
+            DMA0 = 0; DMA1 = 1; DMA2 = 2; DMA3 = 3;
+            DMA4 = 4; DMA5 = 5; DMA6 = 6; DMA7 = 7;
+            DMA8 = 8; DMA9 = 9; DMA10 = 10; DMA11 = 11;
+            DMA12 = 12; DMA13 = 13; DMA14 = 14; DMA15 = 15;
+        );
+    };
+    (unsafe $chip:ident, $dma:path) => {
+        $crate::bind_interrupts!(@paired $chip);
+        $crate::bind_interrupts!(@handlers $dma;
+            DMA0_DMA16 = 0, 16; DMA1_DMA17 = 1, 17; DMA2_DMA18 = 2, 18; DMA3_DMA19 = 3, 19;
+            DMA4_DMA20 = 4, 20; DMA5_DMA21 = 5, 21; DMA6_DMA22 = 6, 22; DMA7_DMA23 = 7, 23;
+            DMA8_DMA24 = 8, 24; DMA9_DMA25 = 9, 25; DMA10_DMA26 = 10, 26; DMA11_DMA27 = 11, 27;
+            DMA12_DMA28 = 12, 28; DMA13_DMA29 = 13, 29; DMA14_DMA30 = 14, 30; DMA15_DMA31 = 15, 31;
+        );
+    };
+    (@paired imxrt1020) => {};
+    (@paired imxrt1050) => {};
+    (@paired imxrt1060) => {};
+    (@paired imxrt1064) => {};
+    (@paired imxrt1170) => {};
+    (@handlers $dma:path; $($vector:ident = $($channel:literal),+;)+) => {
+        $(
+            #[unsafe(no_mangle)]
+            #[allow(non_snake_case)]
+            extern "C" fn $vector() {
+                // Safety: caller acknowledged the safety requirements
+                // with the unsafe keyword.
+                unsafe { $($dma.on_interrupt($channel);)+ }
+            }
+        )+
+
+        #[unsafe(no_mangle)]
+        #[allow(non_snake_case)]
+        extern "C" fn DMA_ERROR() {
+            // Safety: see above.
+            unsafe { $dma.on_interrupt_all() };
+        }
+    };
+}
+
+/// A function called from [`on_interrupt`](crate::Dma::on_interrupt)
+///
+/// The callback receives the DMA channel number, and the channel's status.
+/// Use [`set_callback`](crate::Dma::set_callback) to register a callback.
+pub type Callback = fn(usize, Status);
+
+/// A DMA channel's status, provided to a [`Callback`]
+#[derive(Debug, Clone, Copy)]
+pub enum Status {
+    /// The channel completed its major loop.
+    Complete,
+    /// The channel completed half of its major loop.
+    ///
+    /// See [`set_interrupt_on_half`](crate::channel::Channel::set_interrupt_on_half).
+    HalfComplete,
+    /// The channel is in error.
+    Error(Error),
+}
+
+pub(crate) type SharedCallback = Mutex<Cell<Option<Callback>>>;
+#[allow(clippy::declare_interior_mutable_const)] // See NO_WAKER
+pub(crate) const NO_CALLBACK: SharedCallback = Mutex::new(Cell::new(None));
+
+pub(crate) type SharedWaker = AtomicWaker;
 #[allow(clippy::declare_interior_mutable_const)] // Very convenient, and usage for static init deemed OK in clippy docs
-pub(crate) const NO_WAKER: SharedWaker = Mutex::new(RefCell::new(None));
+pub(crate) const NO_WAKER: SharedWaker = AtomicWaker::new();
+#[allow(clippy::declare_interior_mutable_const)] // See above
+pub(crate) const NO_CANCEL: AtomicBool = AtomicBool::new(false);
+#[allow(clippy::declare_interior_mutable_const)] // See above
+pub(crate) const NO_ERROR_REPORTED: AtomicBool = AtomicBool::new(false);
 
 /// The core DMA transfer future
 ///
@@ -75,7 +291,8 @@ pub(crate) const NO_WAKER: SharedWaker = Mutex::new(RefCell::new(None));
 /// initiate a DMA transfer when it is first polled. You may then poll it
 /// to understand when the transfer completes.
 ///
-/// To cancel a transfer, drop the `Transfer`.
+/// To cancel a transfer, drop the `Transfer`. To cancel a transfer from another
+/// execution context, use [`Dma::cancel`](crate::Dma::cancel).
 ///
 /// If you've enabled DMA interrupts, consider using [`on_interrupt`](crate::Dma::on_interrupt)
 /// to wake an executor when the DMA transfer completes, The interrupt interface assumes that you've
@@ -112,6 +329,8 @@ pub(crate) const NO_WAKER: SharedWaker = Mutex::new(RefCell::new(None));
 /// ```
 pub struct Transfer<'a> {
     channel: &'a Channel,
+    /// Set while the transfer is suspended.
+    suspended: bool,
     _pinned: PhantomPinned,
 }
 
@@ -121,55 +340,226 @@ impl<'a> Transfer<'a> {
     /// # Safety
     ///
     /// Assumes that the transfer is correctly defined in the DMA channel memory.
-    /// The transfer enables after the first call to `poll()`.
+    /// The transfer enables after the first call to `poll()`. To check the
+    /// transfer beforehand, call [`Channel::validate`].
     pub unsafe fn new(channel: &'a Channel) -> Self {
+        acquire_channel(channel);
         Transfer {
             channel,
+            suspended: false,
             _pinned: PhantomPinned,
         }
     }
+
+    /// Suspend the transfer
+    ///
+    /// `suspend` disables the channel, and waits for the channel to finish its
+    /// current minor loop. The channel's transfer control descriptor keeps the
+    /// transfer's progress. While it's suspended, the transfer stays pending.
+    /// It still responds to [`Dma::cancel`](crate::Dma::cancel).
+    ///
+    /// The channel only stops between minor loops. Suspending a transfer that
+    /// has a single minor loop waits for the whole transfer.
+    ///
+    /// Suspending a suspended transfer has no effect.
+    pub fn suspend(self: Pin<&mut Self>) {
+        // Safety: the transfer is never moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        if !this.suspended {
+            suspend_channel(this.channel);
+            this.suspended = true;
+        }
+    }
+
+    /// Resume a suspended transfer
+    ///
+    /// The transfer continues from where [`suspend`](Self::suspend) stopped it.
+    /// `resume` wakes the transfer's waker, and the next poll enables the channel.
+    ///
+    /// Resuming a transfer that isn't suspended has no effect.
+    pub fn resume(self: Pin<&mut Self>) {
+        // Safety: the transfer is never moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.suspended {
+            this.suspended = false;
+            this.channel.waker.wake();
+        }
+    }
+
+    /// Returns `true` if the transfer is suspended
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
 }
 
+/// Implements `suspend`, `resume`, and `is_suspended` for a future that wraps a
+/// [`Transfer`] in its `transfer` field
+///
+/// `$what` names the operation in the generated documentation. A future that
+/// polls its channel with [`poll_transfer`] uses the `channel` form. It needs a
+/// `channel` field, and a `suspended: bool` field.
+macro_rules! suspend_methods {
+    ($what:literal, channel) => {
+        #[doc = concat!("Suspend the ", $what)]
+        ///
+        /// See [`Transfer::suspend`](crate::Transfer::suspend) for more information.
+        pub fn suspend(self: core::pin::Pin<&mut Self>) {
+            // Safety: the future is never moved.
+            let this = unsafe { self.get_unchecked_mut() };
+            if !this.suspended {
+                $crate::interrupt::suspend_channel(&this.channel);
+                this.suspended = true;
+            }
+        }
+
+        #[doc = concat!("Resume a suspended ", $what)]
+        ///
+        /// See [`Transfer::resume`](crate::Transfer::resume) for more information.
+        pub fn resume(self: core::pin::Pin<&mut Self>) {
+            // Safety: the future is never moved.
+            let this = unsafe { self.get_unchecked_mut() };
+            if this.suspended {
+                this.suspended = false;
+                this.channel.waker.wake();
+            }
+        }
+
+        #[doc = concat!("Returns `true` if the ", $what, " is suspended")]
+        pub fn is_suspended(&self) -> bool {
+            self.suspended
+        }
+    };
+    ($what:literal) => {
+        #[doc = concat!("Suspend the ", $what)]
+        ///
+        /// See [`Transfer::suspend`](crate::Transfer::suspend) for more information.
+        pub fn suspend(self: core::pin::Pin<&mut Self>) {
+            // Safety: pin projection OK, the transfer is never moved.
+            unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.suspend();
+        }
+
+        #[doc = concat!("Resume a suspended ", $what)]
+        ///
+        /// See [`Transfer::resume`](crate::Transfer::resume) for more information.
+        pub fn resume(self: core::pin::Pin<&mut Self>) {
+            // Safety: pin projection OK, the transfer is never moved.
+            unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.resume();
+        }
+
+        #[doc = concat!("Returns `true` if the ", $what, " is suspended")]
+        pub fn is_suspended(&self) -> bool {
+            self.transfer.is_suspended()
+        }
+    };
+}
+
+pub(crate) use suspend_methods;
+
 impl Future for Transfer<'_> {
     type Output = Result<(), Error>;
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        interrupt::free(|cs| {
-            let waker = self.channel.waker.borrow(cs);
-            let mut waker = waker.borrow_mut();
-            *waker = Some(cx.waker().clone());
-        });
-
-        loop {
-            // This driver is only expecting to catch synchronous errors
-            // (those that manifest once we enable the transfer). If there
-            // is a misconfiguration that only the hardware detects, we expect
-            // to see it as soon as we loop back around after the enable.
-            if self.channel.is_error() {
-                let es = self.channel.error_status();
-                self.channel.clear_error();
-                return Poll::Ready(Err(es));
-            } else if self.channel.is_complete() {
-                self.channel.clear_complete();
-                return Poll::Ready(Ok(()));
-            } else if self.channel.is_enabled() {
-                return Poll::Pending;
-            } else {
-                atomic::fence(atomic::Ordering::SeqCst);
-                unsafe { self.channel.enable() };
-            }
-        }
+        // Safety: transfer is correctly defined, per `new`'s contract.
+        unsafe { poll_transfer(self.channel, self.suspended, cx) }
     }
 }
 
 impl Drop for Transfer<'_> {
     fn drop(&mut self) {
-        self.channel.disable();
-        self.channel.clear_complete();
-        self.channel.clear_error();
-        interrupt::free(|cs| {
-            let waker = self.channel.waker.borrow(cs);
-            let mut waker = waker.borrow_mut();
-            *waker = None;
-        });
+        release_channel(self.channel);
+    }
+}
+
+/// Prepare the channel for a new transfer future
+///
+/// This discards any cancellation requested before the transfer.
+pub(crate) fn acquire_channel(channel: &Channel) {
+    channel.cancel.store(false, atomic::Ordering::Relaxed);
+}
+
+/// Stop the channel when its transfer future drops
+///
+/// This disables the channel, stops any scatter / gather chain, and
+/// clears the channel's flags and waker.
+pub(crate) fn release_channel(channel: &Channel) {
+    channel.disable();
+    channel.end_scatter_gather();
+    channel.clear_complete();
+    channel.clear_error();
+    channel.cancel.store(false, atomic::Ordering::Relaxed);
+    clear_waker(channel);
+}
+
+/// Disable the channel, and wait for it to finish its current minor loop
+pub(crate) fn suspend_channel(channel: &Channel) {
+    channel.disable();
+    while channel.is_active() {}
+}
+
+/// Poll a transfer future's channel
+///
+/// A `suspended` transfer stays pending until it's cancelled.
+///
+/// # Safety
+///
+/// The channel must describe a valid transfer. See [`Channel::enable`].
+pub(crate) unsafe fn poll_transfer(
+    channel: &Channel,
+    suspended: bool,
+    cx: &mut Context<'_>,
+) -> Poll<Result<(), Error>> {
+    register_waker(channel, cx.waker());
+    if suspended && !channel.cancel.load(atomic::Ordering::Acquire) {
+        return Poll::Pending;
+    }
+    poll_channel(channel)
+}
+
+/// Wake `waker` when the channel's transfer completes
+///
+/// If the channel already holds an equivalent waker, this doesn't
+/// replace it.
+pub(crate) fn register_waker(channel: &Channel, waker: &Waker) {
+    channel.waker.register(waker);
+}
+
+/// Drop the channel's waker
+pub(crate) fn clear_waker(channel: &Channel) {
+    channel.waker.take();
+}
+
+/// Check the status of the channel's transfer, enabling the channel
+/// if necessary
+///
+/// Once this returns `Poll::Ready`, the channel's completion and error
+/// flags are cleared.
+///
+/// # Safety
+///
+/// The channel must describe a valid transfer. See [`Channel::enable`].
+pub(crate) unsafe fn poll_channel(channel: &Channel) -> Poll<Result<(), Error>> {
+    if channel.cancel.swap(false, atomic::Ordering::Acquire) {
+        channel.disable();
+        while channel.is_active() {}
+        return Poll::Ready(Err(Error::cancelled(channel.channel())));
+    }
+
+    loop {
+        // This driver is only expecting to catch synchronous errors
+        // (those that manifest once we enable the transfer). If there
+        // is a misconfiguration that only the hardware detects, we expect
+        // to see it as soon as we loop back around after the enable.
+        if channel.is_error() {
+            let es = channel.error_status();
+            channel.clear_error();
+            return Poll::Ready(Err(es));
+        } else if channel.is_complete() {
+            channel.clear_complete();
+            return Poll::Ready(Ok(()));
+        } else if channel.is_enabled() {
+            return Poll::Pending;
+        } else {
+            atomic::fence(atomic::Ordering::SeqCst);
+            channel.enable();
+        }
     }
 }
@@ -0,0 +1,231 @@
+//! `embedded-io-async` adapters for DMA-capable serial peripherals.
+//!
+//! Enable the `embedded-io-async` feature to use this module.
+//!
+//! [`Writer`] implements [`embedded_io_async::Write`] for any byte-oriented
+//! [`Destination`]. [`Reader`] implements [`embedded_io_async::Read`] for any
+//! byte-oriented [`IdleSource`]. Like [`receive`](peripheral::receive), a read
+//! ends early, returning the number of received bytes, when
+//!
+//! - the peripheral detects an idle line.
+//! - someone cancels the transfer with [`Dma::cancel`](crate::Dma::cancel).
+//!
+//! If the peripheral describes a [`Window`](peripheral::Window) of registers,
+//! each read or write moves a whole number of windows. A buffer that's shorter
+//! than one window is an [`Error::ShortBuffer`].
+
+use crate::{
+    channel::Channel,
+    peripheral::{self, Destination, IdleSource, Source},
+};
+
+use core::fmt::{self, Display};
+use embedded_io_async::{ErrorKind, ErrorType};
+
+/// The maximum number of bytes moved by one read or write
+///
+/// This is the maximum number of DMA transfer iterations.
+const MAX_LEN: usize = 0x7FFF;
+
+/// Returns the number of bytes to move from a buffer of `len` bytes
+///
+/// The result is a whole number of register windows, and it's zero if
+/// `len` is shorter than one window.
+fn window_len(len: usize, registers: u16) -> usize {
+    let registers = registers as usize;
+    len.min(MAX_LEN) / registers * registers
+}
+
+/// An error from a [`Reader`] or [`Writer`]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error {
+    /// The DMA controller reported an error, or the transfer was cancelled.
+    Dma(crate::Error),
+    /// The buffer is shorter than the peripheral's register window.
+    ShortBuffer,
+}
+
+impl From<crate::Error> for Error {
+    fn from(err: crate::Error) -> Self {
+        Error::Dma(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Dma(err) => Display::fmt(err, f),
+            Error::ShortBuffer => f.write_str("buffer is shorter than the register window"),
+        }
+    }
+}
+
+/// Receives bytes from a peripheral using a DMA channel
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::{io::Reader, peripheral};
+/// use embedded_io_async::Read as _;
+/// # use core::task::{Context, Poll};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+/// # unsafe impl peripheral::IdleSource<u8> for X {
+/// #   fn poll_idle(&mut self, _: &mut Context<'_>) -> Poll<()> { panic!() }
+/// # }
+///
+/// # async fn f() -> Result<(), imxrt_dma::io::Error> {
+/// let lpuart = // A LPUART receiver
+///     # X;
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// channel.set_interrupt_on_completion(true);
+///
+/// let mut reader = Reader::new(lpuart, channel);
+/// let mut sentence = [0u8; 82];
+/// let len = reader.read(&mut sentence).await?;
+/// let sentence = &sentence[..len];
+/// # Ok(()) }
+/// ```
+pub struct Reader<S> {
+    source: S,
+    channel: Channel,
+}
+
+impl<S> Reader<S>
+where
+    S: Source<u8>,
+{
+    /// Create a reader from a peripheral and a DMA channel
+    ///
+    /// You're responsible for configuring the channel's interrupt.
+    pub fn new(source: S, channel: Channel) -> Self {
+        Reader { source, channel }
+    }
+
+    /// Returns the peripheral and the DMA channel
+    pub fn release(self) -> (S, Channel) {
+        (self.source, self.channel)
+    }
+}
+
+/// Sends bytes to a peripheral using a DMA channel
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::{io::Writer, peripheral};
+/// use embedded_io_async::Write as _;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u8> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> Result<(), imxrt_dma::io::Error> {
+/// let lpuart = // A LPUART transmitter
+///     # X;
+/// let mut channel = // DMA channel 8
+///     # unsafe { DMA.channel(8) };
+/// channel.set_interrupt_on_completion(true);
+///
+/// let mut writer = Writer::new(lpuart, channel);
+/// writer.write_all(b"AT+CGMI\r\n").await?;
+/// # Ok(()) }
+/// ```
+pub struct Writer<D> {
+    destination: D,
+    channel: Channel,
+}
+
+impl<D> Writer<D>
+where
+    D: Destination<u8>,
+{
+    /// Create a writer from a peripheral and a DMA channel
+    ///
+    /// You're responsible for configuring the channel's interrupt.
+    pub fn new(destination: D, channel: Channel) -> Self {
+        Writer {
+            destination,
+            channel,
+        }
+    }
+
+    /// Returns the peripheral and the DMA channel
+    pub fn release(self) -> (D, Channel) {
+        (self.destination, self.channel)
+    }
+}
+
+impl embedded_io_async::Error for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Dma(_) => ErrorKind::Other,
+            Error::ShortBuffer => ErrorKind::InvalidInput,
+        }
+    }
+}
+
+impl<S> ErrorType for Reader<S> {
+    type Error = Error;
+}
+
+impl<D> ErrorType for Writer<D> {
+    type Error = Error;
+}
+
+impl<S> embedded_io_async::Read for Reader<S>
+where
+    S: IdleSource<u8>,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let len = window_len(buf.len(), self.source.source_window().registers());
+        if len == 0 {
+            return Err(Error::ShortBuffer);
+        }
+        let received = peripheral::receive(&mut self.channel, &mut self.source, &mut buf[..len])
+            .await?
+            .len();
+        if received == 0 {
+            // Only a cancellation ends a receive without data.
+            return Err(crate::Error::cancelled(self.channel.channel()).into());
+        }
+        Ok(received)
+    }
+}
+
+impl<D> embedded_io_async::Write for Writer<D>
+where
+    D: Destination<u8>,
+{
+    /// Write bytes to the peripheral
+    ///
+    /// The write resolves once the DMA channel has moved all bytes into the
+    /// peripheral. The peripheral may still be sending the data.
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let len = window_len(buf.len(), self.destination.destination_window().registers());
+        if len == 0 {
+            return Err(Error::ShortBuffer);
+        }
+        peripheral::write(&mut self.channel, &buf[..len], &mut self.destination).await?;
+        Ok(len)
+    }
+}
@@ -46,11 +46,51 @@
 //! - [`full_duplex`](crate::peripheral::full_duplex) to read / write with a
 //!   peripheral using a single buffer.
 //!
+//! These futures borrow their buffers. For transfers that take ownership of
+//! `'static` buffers, and that run without an executor, see [`owned`]. For
+//! blocking transfers, see [`blocking`]. For transfers with a deadline, see
+//! [`timeout`]. To rearrange data as it's copied, like deinterleaving audio
+//! channels, see [`transform`].
+//!
 //! Peripheral transfers depends on a peripheral's DMA support. These are signaled
 //! through various [`peripheral`] traits.
 //!
-//! For a lower-level API, use the [`channel`] objects and helper
-//! functions.
+//! # Minor loop mapping
+//!
+//! Minor loop offsets, and the [`transform`] module that uses them, require minor loop
+//! mapping. Mapping is a DMA controller setting that's shared by all channels,
+//! so it's off until you opt in with [`Dma::enable_minor_loop_mapping`]. Enable
+//! it once, before you start any transfers.
+//!
+//! # Critical sections
+//!
+//! `imxrt-dma` uses the [`critical-section`](https://docs.rs/critical-section)
+//! crate to share [`Callback`]s with interrupt handlers. Your final program
+//! must provide a critical section implementation. On a single-core system,
+//! enable the `critical-section-single-core` feature of `cortex-m`. On a
+//! multi-core system, like the i.MX RT 1170, provide an implementation that
+//! synchronizes the cores. Transfer futures don't take a critical section
+//! when they're polled.
+//!
+//! To split the DMA channels between cores, give each core a [`Partition`].
+//!
+//! # Optional features
+//!
+//! - `dcache`, enabled by default, performs data cache maintenance on ARM targets.
+//!   Disable it for cores without a data cache, like the i.MX RT 1170's Cortex-M4.
+//!   See the [`cache`] module.
+//! - `embedded-hal-async` provides a `spi::SpiBus` that implements the
+//!   `embedded-hal-async` SPI bus trait for bidirectional peripherals.
+//! - `defmt` implements `defmt::Format` for [`Error`] and for the channel
+//!   [`State`](channel::State), which decodes a channel's flags, DMAMUX
+//!   configuration, and transfer control descriptor.
+//! - `embedded-io-async` provides `io::Reader` and `io::Writer`, which implement
+//!   the `embedded-io-async` `Read` and `Write` traits for DMA-capable serial
+//!   peripherals.
+//!
+//! To describe a custom transfer without unsafe code, use a
+//! [`Builder`](builder::Builder). For a lower-level API, use the [`channel`]
+//! objects and helper functions.
 //!
 //! ### License
 //!
@@ -67,17 +107,31 @@
 
 #![no_std]
 
+pub mod blocking;
+pub mod builder;
+pub mod cache;
 pub mod channel;
 mod element;
 mod error;
 mod interrupt;
+#[cfg(feature = "embedded-io-async")]
+pub mod io;
 pub mod memcpy;
+pub mod owned;
+mod partition;
 pub mod peripheral;
+mod power;
 mod ral;
+#[cfg(feature = "embedded-hal-async")]
+pub mod spi;
+pub mod timeout;
+pub mod transform;
 
 pub use element::Element;
-pub use error::Error;
-pub use interrupt::Transfer;
+pub use error::{Error, ErrorClass, ErrorKind, ErrorKinds, ValidationError};
+pub use interrupt::{Callback, Status, Transfer};
+pub use partition::Partition;
+pub use power::Quiesce;
 pub use ral::tcd::BandwidthControl;
 
 /// A DMA result
@@ -94,6 +148,13 @@ pub struct Dma<const CHANNELS: usize> {
     controller: ral::Static<ral::dma::RegisterBlock>,
     multiplexer: ral::Static<ral::dmamux::RegisterBlock>,
     wakers: [SharedWaker; CHANNELS],
+    cancels: [AtomicBool; CHANNELS],
+    /// Set once a channel's error is reported to its callback.
+    errors_reported: [AtomicBool; CHANNELS],
+    callbacks: [SharedCallback; CHANNELS],
+    /// Woken when a channel completes, or has an error. One per channel,
+    /// so that partitions may wait for their own channels.
+    quiesce: [SharedWaker; CHANNELS],
 }
 
 // Safety: OK to allocate a DMA driver in a static context.
@@ -124,8 +185,13 @@ impl<const CHANNELS: usize> Dma<CHANNELS> {
             controller: ral::Static(controller.cast()),
             multiplexer: ral::Static(multiplexer.cast()),
             wakers: [NO_WAKER; CHANNELS],
+            cancels: [NO_CANCEL; CHANNELS],
+            errors_reported: [NO_ERROR_REPORTED; CHANNELS],
+            callbacks: [NO_CALLBACK; CHANNELS],
+            quiesce: [NO_WAKER; CHANNELS],
         }
     }
 }
 
-use interrupt::{SharedWaker, NO_WAKER};
+use core::sync::atomic::AtomicBool;
+use interrupt::{SharedCallback, SharedWaker, NO_CALLBACK, NO_CANCEL, NO_ERROR_REPORTED, NO_WAKER};
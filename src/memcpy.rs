@@ -1,13 +1,17 @@
 //! DMA-powered memcpy
 
 use crate::{
-    channel::{self, Channel},
-    interrupt::Transfer,
+    builder::{Builder, Prepared},
+    cache,
+    channel::{self, Channel, Descriptor},
+    element::Burst,
+    interrupt::{self, suspend_methods, Transfer},
     Element, Error,
 };
 
 use core::{
     future::Future,
+    marker::PhantomPinned,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -18,16 +22,44 @@ use core::{
 /// buffers. Use the [`memcpy`] function to define the transfer.
 pub struct Memcpy<'a, E> {
     transfer: Transfer<'a>,
+    cache: cache::Invalidate,
     channel: &'a Channel,
+    /// Start the channel whenever it's idle. A scatter / gather chain
+    /// starts its own descriptors, so it's started only once.
+    restart: bool,
+    /// Set after the first software start.
+    started: bool,
+    /// Set if there's nothing to copy. The future resolves without
+    /// enabling the channel.
+    empty: bool,
+    /// Bytes that the CPU copies once the channel completes.
+    edges: Option<Edges<'a>>,
     _elem: core::marker::PhantomData<(&'a E, &'a mut E)>,
 }
 
+/// The unaligned bytes at the start and end of a [`memcpy_bytes`] copy
+struct Edges<'a> {
+    head: (&'a [u8], &'a mut [u8]),
+    tail: (&'a [u8], &'a mut [u8]),
+}
+
+impl Edges<'_> {
+    fn copy(self) {
+        self.head.1.copy_from_slice(self.head.0);
+        self.tail.1.copy_from_slice(self.tail.0);
+    }
+}
+
 /// Perform a DMA-powered `memcpy` between the `source` and `destination` buffers
 ///
-/// Copies the minimum number of elements between the two buffers. You're responsible
+/// Copies the minimum number of elements between the two buffers. If either buffer
+/// is empty, the future resolves without enabling the channel. You're responsible
 /// for enabling any interrupts, and calling [`on_interrupt`](crate::Dma::on_interrupt)
 /// if the interrupt fires. Otherwise, you may poll the transfer until completion.
 ///
+/// If the channel [maintains the data cache](Channel::set_cache_maintenance), `memcpy`
+/// cleans the source, and invalidates the destination when the transfer ends.
+///
 /// # Example
 ///
 /// Transfer 5 `u32`s between a source and destination buffer. The transfer completes when
@@ -60,10 +92,458 @@ pub fn memcpy<'a, E: Element>(
     destination: &'a mut [E],
     channel: &'a mut Channel,
 ) -> Memcpy<'a, E> {
+    let bytes = prepare_memcpy(source, destination, channel);
+
+    Memcpy {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        cache: cache::Invalidate::new(channel, destination),
+        channel,
+        restart: true,
+        started: false,
+        empty: bytes == 0,
+        edges: None,
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// A chunked memcpy operation
+///
+/// `Chunked` yields when it's moved the minimum amount of elements between two linear
+/// buffers. Use the [`memcpy_chunked`] function to define the transfer. A suspended
+/// `Chunked` finishes the chunk in progress before it stops.
+pub struct Chunked<'a, E> {
+    /// Reprogrammed for the final minor loop.
+    channel: &'a mut Channel,
+    /// Set while the memcpy is suspended.
+    suspended: bool,
+    _cache: cache::Invalidate,
+    /// The size of the final minor loop, if the chunks don't evenly divide the copy.
+    remainder: u32,
+    /// Set if there's nothing to copy. The future resolves without
+    /// enabling the channel.
+    empty: bool,
+    _pinned: PhantomPinned,
+    _elem: core::marker::PhantomData<(&'a E, &'a mut E)>,
+}
+
+/// Perform a DMA-powered `memcpy` in minor loops of `chunk` elements
+///
+/// [`memcpy`] copies all elements in one minor loop. The DMA controller only
+/// arbitrates between channels after a minor loop, so a long `memcpy` delays
+/// other channels. `memcpy_chunked` copies `chunk` elements per minor loop,
+/// letting higher-priority channels service their requests between chunks.
+/// If `chunk` doesn't evenly divide the copy, the final minor loop copies the
+/// remaining elements.
+///
+/// The copy runs on an [`AlwaysOn`](channel::Configuration::AlwaysOn) channel.
+/// To throttle the copy further, set the channel's
+/// [bandwidth control](Channel::set_bandwidth_control). Otherwise, this behaves
+/// like [`memcpy`].
+///
+/// # Example
+///
+/// Copy 4KiB in 64 byte chunks, stalling between each read and write.
+///
+/// ```no_run
+/// use imxrt_dma::{memcpy, BandwidthControl};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// channel.set_bandwidth_control(Some(BandwidthControl::Stall4Cycles));
+///
+/// let source = [0u32; 1024];
+/// let mut destination = [0u32; 1024];
+///
+/// memcpy::memcpy_chunked(&source, &mut destination, 16, &mut channel).await?;
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if `chunk` is zero, or if the copy needs more than 32767 chunks.
+pub fn memcpy_chunked<'a, E: Element>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    chunk: usize,
+    channel: &'a mut Channel,
+) -> Chunked<'a, E> {
+    assert!(chunk > 0, "DMA memcpy chunk is empty");
+    let len = source.len().min(destination.len());
+    let chunk = chunk.min(len.max(1));
+    let (chunks, remainder) = (len / chunk, len % chunk);
+    assert!(chunks <= 0x7FFF, "DMA memcpy needs more than 32767 chunks");
+
+    prepare_memcpy(source, destination, channel);
+    // Safety: the chunks cover at most the minimum number of elements.
+    // Without last address adjustments, the channel points at the
+    // remainder once the chunks are copied.
+    unsafe {
+        channel.set_minor_loop_bytes((chunk * core::mem::size_of::<E>()) as u32);
+        channel.set_transfer_iterations(chunks as u16);
+        channel.set_source_last_address_adjustment(0);
+        channel.set_destination_last_address_adjustment(0);
+    }
+    channel.set_channel_configuration(channel::Configuration::AlwaysOn);
+
+    interrupt::acquire_channel(channel);
+    Chunked {
+        _cache: cache::Invalidate::new(channel, destination),
+        channel,
+        suspended: false,
+        remainder: (remainder * core::mem::size_of::<E>()) as u32,
+        empty: len == 0,
+        _pinned: PhantomPinned,
+        _elem: core::marker::PhantomData,
+    }
+}
+
+impl<E> Chunked<'_, E> {
+    suspend_methods!("memcpy", channel);
+}
+
+impl<E> Future for Chunked<'_, E> {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: data not moved
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.empty {
+            return Poll::Ready(Ok(()));
+        }
+        loop {
+            // Safety: memcpy_chunked prepared the transfer, and the buffers
+            // are borrowed for 'a.
+            let poll = unsafe { interrupt::poll_transfer(this.channel, this.suspended, cx) };
+            match poll {
+                Poll::Ready(Ok(())) if this.remainder != 0 => {
+                    // Safety: the channel completed, so it's disabled. It points
+                    // at the remaining elements in both buffers.
+                    unsafe { this.channel.set_final_minor_loop(this.remainder) };
+                    this.remainder = 0;
+                }
+                poll => return poll,
+            }
+        }
+    }
+}
+
+impl<E> Drop for Chunked<'_, E> {
+    fn drop(&mut self) {
+        interrupt::release_channel(self.channel);
+    }
+}
+
+/// Perform a DMA-powered `memcpy` between buffers of different element widths
+///
+/// The DMA channel reads the source with `S`-wide accesses, and writes the
+/// destination with `D`-wide accesses. It copies the minimum number of bytes
+/// between the two buffers, rounded down to a multiple of the wider element.
+/// If that's zero, the future resolves without copying anything. Otherwise,
+/// this behaves like [`memcpy`].
+///
+/// ```no_run
+/// use imxrt_dma::memcpy;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let source = [0x01u8, 0x02, 0x03, 0x04];
+/// let mut destination = [0u32; 1];
+///
+/// memcpy::memcpy_mixed(&source, &mut destination, &mut channel).await?;
+/// assert_eq!(destination, [0x04030201]);
+/// # Ok(()) }
+/// ```
+pub fn memcpy_mixed<'a, S: Element, D: Element>(
+    source: &'a [S],
+    destination: &'a mut [D],
+    channel: &'a mut Channel,
+) -> Memcpy<'a, D> {
+    let bytes = prepare_memcpy(source, destination, channel);
+
+    Memcpy {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        cache: cache::Invalidate::new(channel, destination),
+        channel,
+        restart: true,
+        started: false,
+        empty: bytes == 0,
+        edges: None,
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Perform a DMA-powered `memcpy` between two byte buffers
+///
+/// Copies the minimum number of bytes between the two buffers. `memcpy_bytes`
+/// inspects the buffers' alignment, and uses the widest transfer size that both
+/// buffers allow: 32-byte bursts, or 8, 4, 2, or 1 byte elements. The CPU copies
+/// any unaligned bytes at the start and end of the buffers after the DMA channel
+/// completes its part. If the transfer fails, or if you drop the future before it
+/// resolves, the CPU doesn't copy those bytes. Otherwise, this behaves like [`memcpy`].
+///
+/// The buffers only allow a wide transfer size if their addresses have the
+/// same alignment offset. For the widest transfers, align both buffers to 32
+/// bytes, as with [`cache::Aligned`].
+///
+/// If the channel [maintains the data cache](Channel::set_cache_maintenance),
+/// only the DMA channel's part of the destination is maintained. The unaligned
+/// head and tail bytes usually share cache lines with that part. The CPU copies
+/// them after it invalidates the DMA channel's part, so the invalidation doesn't
+/// discard them. Like any shared line, don't access the destination until the
+/// future resolves, or the cache may overwrite the DMA channel's writes. See the
+/// [cache module](crate::cache#alignment).
+///
+/// ```no_run
+/// use imxrt_dma::{cache::Aligned, memcpy};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let source = Aligned([1u8; 1024]);
+/// let mut destination = Aligned([0u8; 1024]);
+///
+/// // 32-byte bursts.
+/// memcpy::memcpy_bytes(&source.0, &mut destination.0, &mut channel).await?;
+/// # Ok(()) }
+/// ```
+pub fn memcpy_bytes<'a>(
+    source: &'a [u8],
+    destination: &'a mut [u8],
+    channel: &'a mut Channel,
+) -> Memcpy<'a, u8> {
+    let len = source.len().min(destination.len());
+    let (width, head, tail) = split(source.as_ptr() as usize, destination.as_ptr() as usize, len);
+
+    let (source_head, source) = source[..len].split_at(head);
+    let (source, source_tail) = source.split_at(tail - head);
+    let (destination_head, destination) = destination[..len].split_at_mut(head);
+    let (destination, destination_tail) = destination.split_at_mut(tail - head);
+
+    let cache = match width {
+        32 => prepare_memcpy_bytes::<Burst>(source, destination, channel),
+        8 => prepare_memcpy_bytes::<u64>(source, destination, channel),
+        4 => prepare_memcpy_bytes::<u32>(source, destination, channel),
+        2 => prepare_memcpy_bytes::<u16>(source, destination, channel),
+        _ => prepare_memcpy_bytes::<u8>(source, destination, channel),
+    };
+
+    Memcpy {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        cache,
+        channel,
+        restart: true,
+        started: false,
+        empty: head == tail,
+        edges: Some(Edges {
+            head: (source_head, destination_head),
+            tail: (source_tail, destination_tail),
+        }),
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Splits a `len` byte copy between `saddr` and `daddr` for the DMA channel
+///
+/// Returns the DMA channel's transfer width, and the start and end of its part
+/// of the copy. The CPU copies the bytes before the start, and from the end.
+fn split(saddr: usize, daddr: usize, len: usize) -> (usize, usize, usize) {
+    let width = [32, 8, 4, 2]
+        .into_iter()
+        .find(|&width| saddr % width == daddr % width && len >= head_len(saddr, width) + width)
+        .unwrap_or(1);
+    let head = head_len(saddr, width);
+    let tail = head + (len - head) / width * width;
+    (width, head, tail)
+}
+
+/// Returns the number of bytes before `address` is aligned to `width`
+fn head_len(address: usize, width: usize) -> usize {
+    (width - address % width) % width
+}
+
+/// Prepare a memcpy of two byte buffers with elements of type `W`
+///
+/// Both buffers must be aligned to `W`, and they must hold a whole number of `W`.
+fn prepare_memcpy_bytes<W: Element>(
+    source: &[u8],
+    destination: &mut [u8],
+    channel: &mut Channel,
+) -> cache::Invalidate {
+    let len = source.len() / core::mem::size_of::<W>();
+    debug_assert!(source.as_ptr().cast::<W>().is_aligned());
+    debug_assert!(destination.as_ptr().cast::<W>().is_aligned());
+    // Safety: the caller aligned both buffers to W. W is a plain integer,
+    // or an array of integers, so any bytes are a valid W.
+    let (source, destination) = unsafe {
+        (
+            core::slice::from_raw_parts(source.as_ptr().cast::<W>(), len),
+            core::slice::from_raw_parts_mut(destination.as_mut_ptr().cast::<W>(), len),
+        )
+    };
+    prepare_memcpy(source, destination, channel);
+    cache::Invalidate::new(channel, destination)
+}
+
+/// Program `channel` to copy the minimum number of elements between the
+/// `source` and `destination` buffers
+///
+/// Unlike [`memcpy`], the returned [`Prepared`] transfer runs many times.
+/// Each [`run`](Prepared::run) only enables the channel. Use a prepared
+/// memcpy when you repeat the same copy in a tight loop.
+///
+/// ```no_run
+/// use imxrt_dma::memcpy;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let source = [4u32, 5, 6, 7, 8];
+/// let mut destination = [0; 5];
+///
+/// let mut copy = memcpy::prepare(&source, &mut destination, &mut channel);
+/// for _ in 0..1000 {
+///     copy.run().await?;
+/// }
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if either buffer is empty.
+pub fn prepare<'a, E: Element>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    channel: &'a mut Channel,
+) -> Prepared<'a, E> {
+    let len = source.len().min(destination.len());
+    Builder::new()
+        .source_buffer(source)
+        .destination_buffer(destination)
+        .minor_loop(len as u32)
+        .iterations(1)
+        .build(channel)
+        .expect("DMA memcpy buffers are empty")
+}
+
+/// Perform a DMA-powered `memcpy` from many `sources` into one `destination` buffer
+///
+/// The channel copies each source in turn, packing the data into the
+/// destination. The copy stops when the destination is full. The transfer
+/// resolves once, after the last copy. It skips empty sources.
+///
+/// Supply one [`Descriptor`] for each source that contributes data. The
+/// transfer borrows the descriptors until it's dropped.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Descriptor, memcpy};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let header = [1u32, 2];
+/// let payload = [3u32, 4, 5];
+/// let mut destination = [0; 5];
+/// let mut descriptors = [Descriptor::new(); 2];
+///
+/// memcpy::memcpy_vectored(&[&header, &payload], &mut destination, &mut descriptors, &mut channel)
+///     .await?;
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if there's no data to copy, or if there are fewer descriptors than
+/// sources that contribute data.
+pub fn memcpy_vectored<'a, E: Element>(
+    sources: &'a [&'a [E]],
+    destination: &'a mut [E],
+    descriptors: &'a mut [Descriptor],
+    channel: &'a mut Channel,
+) -> Memcpy<'a, E> {
+    let count = segments(sources, destination.len()).count();
+    assert!(count > 0, "DMA vectored memcpy has no data");
+    assert!(
+        descriptors.len() >= count,
+        "DMA vectored memcpy needs {count} descriptors"
+    );
+    let descriptors = &mut descriptors[..count];
+
+    let (first, _) = segments(sources, destination.len()).next().unwrap();
+    prepare_memcpy(first, destination, channel);
+    let template = channel.descriptor();
+    for (descriptor, (source, offset)) in descriptors
+        .iter_mut()
+        .zip(segments(sources, destination.len()))
+    {
+        cache::prepare_source(channel, source);
+        *descriptor = template;
+        descriptor.set_source(source.as_ptr(), source.len());
+        descriptor.set_destination(destination[offset..].as_ptr(), source.len());
+        descriptor.set_minor_loop_bytes(core::mem::size_of_val(source) as u32);
+        descriptor.set_transfer_iterations(1);
+    }
+    // Safety: each segment fits within its source and the destination. Buffers
+    // and descriptors are borrowed for 'a.
+    unsafe { channel::set_scatter_gather(channel, descriptors, true) };
+
+    Memcpy {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        cache: cache::Invalidate::new(channel, destination),
+        channel,
+        restart: false,
+        started: false,
+        empty: false,
+        edges: None,
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Returns each non-empty part of `sources` that fits in `capacity` elements,
+/// along with its offset into the destination
+fn segments<'s, E>(
+    sources: &'s [&'s [E]],
+    capacity: usize,
+) -> impl Iterator<Item = (&'s [E], usize)> {
+    sources
+        .iter()
+        .scan(0, move |offset, source| {
+            let len = source.len().min(capacity - *offset);
+            let segment = (&source[..len], *offset);
+            *offset += len;
+            Some(segment)
+        })
+        .filter(|(segment, _)| !segment.is_empty())
+}
+
+/// Prepare a channel to copy the minimum number of elements between
+/// the two buffers
+///
+/// Returns the number of bytes to copy. If it's zero, the channel must not
+/// be enabled. The caller is responsible for keeping the buffers valid for
+/// the duration of the transfer.
+pub(crate) fn prepare_memcpy<S: Element, D: Element>(
+    source: &[S],
+    destination: &mut [D],
+    channel: &mut Channel,
+) -> u32 {
     channel.disable();
 
     channel.set_disable_on_completion(true);
 
+    cache::prepare_source(channel, source);
+    cache::prepare_destination(channel, destination);
+
     // Safety: buffers borrowed by `memcpy`, and will be valid
     // while a transfer is in progress.
     unsafe {
@@ -71,45 +551,94 @@ pub fn memcpy<'a, E: Element>(
         channel::set_destination_linear_buffer(channel, destination);
     }
 
-    // Turn off any DMAMUX configuration.
-    //
-    // Alternatively, we could use an always-on transfer, which might not need an
-    // explicit "start()" activation. This means we could express the transfer
-    // as a series of major loops, each transferring sizeof(E) bytes in the minor
-    // loop. TBD...
+    // Turn off any DMAMUX configuration. Software starts the single minor
+    // loop. See memcpy_chunked for a copy that runs as a series of minor
+    // loops on an always-on channel.
     channel.set_channel_configuration(channel::Configuration::Off);
 
-    // Transfer all elements in a single major loop
-    //
+    // Transfer all elements in a single major loop. The minor loop must
+    // be a multiple of both element sizes.
+    let widest = core::mem::size_of::<S>().max(core::mem::size_of::<D>());
+    let bytes = core::mem::size_of_val(source).min(core::mem::size_of_val(destination));
+    let bytes = (bytes - bytes % widest) as u32;
     // Safety: transferring the minimum number of bytes between buffers,
     // and there's only one major loop to perform the transfer.
     unsafe {
-        channel.set_minor_loop_bytes(
-            core::mem::size_of::<E>().saturating_mul(source.len().min(destination.len())) as u32,
-        );
+        channel.set_minor_loop_bytes(bytes);
         channel.set_transfer_iterations(1);
     }
+    bytes
+}
 
-    Memcpy {
-        // Safety: transfer is properly prepared
-        transfer: unsafe { Transfer::new(channel) },
-        channel,
-        _elem: core::marker::PhantomData,
+impl<'a, E> Memcpy<'a, E> {
+    /// Returns the DMA channel used by this transfer
+    pub(crate) fn channel(&self) -> &'a Channel {
+        self.channel
     }
 }
 
 impl<E> Future for Memcpy<'_, E> {
     type Output = Result<(), Error>;
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        // Safety: data not moved
-        let transfer = unsafe { self.as_mut().map_unchecked_mut(|this| &mut this.transfer) };
-        let poll = transfer.poll(cx);
-        if poll.is_pending() && !self.channel.is_active() {
-            self.channel.start();
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: pin projection OK, the transfer is never moved, and the
+        // flags are never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let poll = if this.empty {
+            Poll::Ready(Ok(()))
+        } else {
+            let transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+            transfer.poll(cx)
+        };
+        if poll.is_pending() && !this.channel.is_active() && (this.restart || !this.started) {
+            this.channel.start();
+            this.started = true;
+        }
+        if let Poll::Ready(Ok(())) = poll {
+            if let Some(edges) = this.edges.take() {
+                // The channel is done with the destination. Invalidate it first,
+                // so that the invalidation doesn't discard the CPU's copy.
+                this.cache.invalidate();
+                edges.copy();
+            }
         }
         poll
     }
 }
 
-// Drop handled by Transfer impl
+// Drop handled by Transfer and Invalidate impls
+
+#[cfg(test)]
+mod tests {
+    use super::split;
+
+    #[test]
+    fn split_aligned() {
+        assert_eq!(split(0x2000_0000, 0x2000_1000, 1024), (32, 0, 1024));
+        assert_eq!(split(0x2000_0000, 0x2000_1000, 1030), (32, 0, 1024));
+    }
+
+    #[test]
+    fn split_common_offset() {
+        // Both 8 bytes past a 32 byte boundary.
+        assert_eq!(split(0x2000_0008, 0x2000_1008, 100), (32, 24, 88));
+        // Both 4 bytes past an 8 byte boundary, and not enough for a burst.
+        assert_eq!(split(0x2000_0004, 0x2000_1004, 20), (8, 4, 20));
+        assert_eq!(split(0x2000_0002, 0x2000_1006, 11), (4, 2, 10));
+        assert_eq!(split(0x2000_0001, 0x2000_1003, 8), (2, 1, 7));
+    }
+
+    #[test]
+    fn split_bytes() {
+        assert_eq!(split(0x2000_0000, 0x2000_1001, 100), (1, 0, 100));
+        assert_eq!(split(0x2000_0001, 0x2000_1001, 1), (1, 0, 1));
+        assert_eq!(split(0x2000_0000, 0x2000_1000, 0), (1, 0, 0));
+    }
+
+    #[test]
+    fn split_too_short_for_aligned_part() {
+        // Aligning to 4 would leave no room for a 4 byte element.
+        assert_eq!(split(0x2000_0001, 0x2000_1001, 6), (2, 1, 5));
+        assert_eq!(split(0x2000_0003, 0x2000_1003, 2), (1, 0, 2));
+    }
+}
@@ -0,0 +1,676 @@
+//! Ownership-based DMA transfers.
+//!
+//! The futures in [`memcpy`](mod@crate::memcpy) and [`peripheral`]
+//! borrow their buffers. If you [`forget`](core::mem::forget) one of those futures
+//! while the transfer is in progress, the DMA channel keeps accessing memory that's
+//! no longer borrowed. The transfers in this module avoid that problem by taking
+//! ownership of `'static` buffers, the DMA channel, and any peripheral. The buffers
+//! are described with the [`ReadBuffer`] and [`WriteBuffer`] traits from
+//! [`embedded-dma`](https://docs.rs/embedded-dma).
+//!
+//! A transfer starts as soon as you create it, and it runs independently of any
+//! executor. Call `wait()` to spin until the transfer completes, or `.await` the
+//! transfer. Either way, you'll receive the transfer result and all of the
+//! resources that you moved into the transfer. To cancel a transfer, drop it; the
+//! drop waits for the DMA channel to stop.
+//!
+//! # Example
+//!
+//! Send a `'static` buffer to a LPUART peripheral without an executor.
+//!
+//! ```no_run
+//! use imxrt_dma::{owned, peripheral};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct X;
+//! # unsafe impl peripheral::Destination<u8> for X {
+//! #   fn destination_signal(&self) -> u32 { 0 }
+//! #   fn destination_address(&self) -> *const u8 { panic!() }
+//! #   fn enable_destination(&mut self) { panic!() }
+//! #   fn disable_destination(&mut self) { panic!() }
+//! # }
+//!
+//! static MESSAGE: [u8; 5] = *b"hello";
+//!
+//! let lpuart = // A LPUART peripheral
+//!     # X;
+//! let channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//!
+//! let transfer = owned::write(channel, &MESSAGE, lpuart);
+//! // Do other work...
+//! let (result, channel, message, lpuart) = transfer.wait();
+//! result.unwrap();
+//! ```
+
+use crate::{
+    cache,
+    channel::Channel,
+    interrupt, memcpy,
+    peripheral::{self, Bidirectional, Destination, Source},
+    Element, Error,
+};
+
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic,
+    task::{Context, Poll},
+};
+
+use embedded_dma::{ReadBuffer, WriteBuffer};
+
+const POLLED_AFTER_COMPLETION: &str = "DMA transfer polled after completion";
+
+/// Enable a prepared channel
+///
+/// This discards any cancellation requested before the transfer.
+///
+/// # Safety
+///
+/// The channel must describe a valid transfer.
+unsafe fn enable(channel: &Channel) {
+    interrupt::acquire_channel(channel);
+    atomic::fence(atomic::Ordering::SeqCst);
+    channel.enable();
+}
+
+/// Stop a channel, and reset its transfer state
+fn stop(channel: &Channel) {
+    channel.disable();
+    while channel.is_active() {}
+    channel.clear_complete();
+    channel.clear_error();
+    channel.cancel.store(false, atomic::Ordering::Relaxed);
+    interrupt::clear_waker(channel);
+}
+
+/// Check a channel's transfer without enabling the channel
+///
+/// The channel was enabled when the transfer was created. Once the
+/// channel reports completion, an error, or a cancellation, the
+/// flags are cleared.
+fn poll_status(channel: &Channel) -> Poll<Result<(), Error>> {
+    if channel.cancel.swap(false, atomic::Ordering::Acquire) {
+        channel.disable();
+        while channel.is_active() {}
+        Poll::Ready(Err(Error::cancelled(channel.channel())))
+    } else if channel.is_error() {
+        let es = channel.error_status();
+        channel.clear_error();
+        Poll::Ready(Err(es))
+    } else if channel.is_complete() {
+        channel.clear_complete();
+        Poll::Ready(Ok(()))
+    } else {
+        Poll::Pending
+    }
+}
+
+/// Returns the buffer's memory as a slice
+///
+/// # Safety
+///
+/// The slice must only be used while the buffer is owned by the transfer.
+unsafe fn read_slice<'a, B>(buffer: &B) -> &'a [B::Word]
+where
+    B: ReadBuffer,
+{
+    let (ptr, len) = buffer.read_buffer();
+    core::slice::from_raw_parts(ptr, len)
+}
+
+/// Returns the buffer's memory as a mutable slice
+///
+/// # Safety
+///
+/// The slice must only be used while the buffer is owned by the transfer.
+unsafe fn write_slice<'a, B>(buffer: &mut B) -> &'a mut [B::Word]
+where
+    B: WriteBuffer,
+{
+    let (ptr, len) = buffer.write_buffer();
+    core::slice::from_raw_parts_mut(ptr, len)
+}
+
+/// An ownership-based memcpy
+///
+/// Use [`memcpy()`] to create this transfer. The transfer's output is
+///
+/// ```text
+/// (result, source, destination, channel)
+/// ```
+pub struct Memcpy<S, D>
+where
+    S: ReadBuffer,
+    S::Word: Element,
+    D: WriteBuffer<Word = S::Word>,
+{
+    parts: Option<(S, D, Channel)>,
+    invalidate: cache::Invalidate,
+    /// Set if there's nothing to copy. The channel is never enabled.
+    empty: bool,
+}
+
+/// Perform a DMA-powered memcpy between the `source` and `destination` buffers
+///
+/// Copies the minimum number of elements between the two buffers. The transfer
+/// starts immediately. If either buffer is empty, the transfer is complete
+/// without enabling the channel.
+pub fn memcpy<S, D>(source: S, mut destination: D, mut channel: Channel) -> Memcpy<S, D>
+where
+    S: ReadBuffer,
+    S::Word: Element,
+    D: WriteBuffer<Word = S::Word>,
+{
+    // Safety: the buffers are valid, and stay in place, while we own them.
+    // We own them until the transfer stops.
+    let (src, dst) = unsafe { (read_slice(&source), write_slice(&mut destination)) };
+    let empty = memcpy::prepare_memcpy(src, dst, &mut channel) == 0;
+    let invalidate = cache::Invalidate::new(&channel, dst);
+    if !empty {
+        // Safety: transfer prepared above, and it copies at least one element.
+        unsafe { enable(&channel) };
+        channel.start();
+    }
+
+    Memcpy {
+        parts: Some((source, destination, channel)),
+        invalidate,
+        empty,
+    }
+}
+
+impl<S, D> Memcpy<S, D>
+where
+    S: ReadBuffer,
+    S::Word: Element,
+    D: WriteBuffer<Word = S::Word>,
+{
+    /// Returns `true` if the transfer has completed, or produced an error
+    pub fn is_complete(&self) -> bool {
+        self.empty
+            || self
+                .parts
+                .as_ref()
+                .is_none_or(|(_, _, channel)| is_complete(channel))
+    }
+
+    /// Spin until the transfer completes, then return the result and resources
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transfer already returned its resources through `.await`.
+    pub fn wait(mut self) -> (Result<(), Error>, S, D, Channel) {
+        loop {
+            if let Poll::Ready(result) = self.poll_status() {
+                return self.finish(result);
+            }
+        }
+    }
+
+    /// Check the transfer without enabling the channel
+    fn poll_status(&self) -> Poll<Result<(), Error>> {
+        let (_, _, channel) = self.parts.as_ref().expect(POLLED_AFTER_COMPLETION);
+        if self.empty {
+            Poll::Ready(Ok(()))
+        } else {
+            poll_status(channel)
+        }
+    }
+
+    fn finish(&mut self, result: Result<(), Error>) -> (Result<(), Error>, S, D, Channel) {
+        let (source, destination, channel) = self.parts.take().expect(POLLED_AFTER_COMPLETION);
+        stop(&channel);
+        self.invalidate.invalidate();
+        (result, source, destination, channel)
+    }
+}
+
+impl<S, D> Future for Memcpy<S, D>
+where
+    S: ReadBuffer,
+    S::Word: Element,
+    D: WriteBuffer<Word = S::Word>,
+{
+    type Output = (Result<(), Error>, S, D, Channel);
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let (_, _, channel) = this.parts.as_ref().expect(POLLED_AFTER_COMPLETION);
+        interrupt::register_waker(channel, cx.waker());
+        this.poll_status().map(|result| this.finish(result))
+    }
+}
+
+impl<S, D> Unpin for Memcpy<S, D>
+where
+    S: ReadBuffer,
+    S::Word: Element,
+    D: WriteBuffer<Word = S::Word>,
+{
+}
+
+impl<S, D> Drop for Memcpy<S, D>
+where
+    S: ReadBuffer,
+    S::Word: Element,
+    D: WriteBuffer<Word = S::Word>,
+{
+    fn drop(&mut self) {
+        if let Some((_, _, channel)) = &self.parts {
+            stop(channel);
+        }
+    }
+}
+
+/// An ownership-based transfer that receives data from hardware
+///
+/// Use [`read()`] to create this transfer. The transfer's output is
+///
+/// ```text
+/// (result, channel, source, buffer)
+/// ```
+pub struct Read<S, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    S: Source<B::Word>,
+{
+    parts: Option<(Channel, S, B)>,
+    invalidate: cache::Invalidate,
+}
+
+/// Use a DMA channel to receive a `buffer` of elements from the source peripheral
+///
+/// The transfer starts immediately. See [`peripheral::read`]
+/// for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the source's window size.
+pub fn read<S, B>(mut channel: Channel, mut source: S, mut buffer: B) -> Read<S, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    S: Source<B::Word>,
+{
+    // Safety: the buffer is valid, and stays in place, while we own it.
+    // We own it until the transfer stops.
+    let slice = unsafe { write_slice(&mut buffer) };
+    peripheral::prepare_read(&mut channel, &mut source, slice);
+    let invalidate = cache::Invalidate::new(&channel, slice);
+    // Safety: transfer prepared above.
+    unsafe { enable(&channel) };
+
+    Read {
+        parts: Some((channel, source, buffer)),
+        invalidate,
+    }
+}
+
+impl<S, B> Read<S, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    S: Source<B::Word>,
+{
+    /// Returns `true` if the transfer has completed, or produced an error
+    pub fn is_complete(&self) -> bool {
+        self.parts
+            .as_ref()
+            .is_none_or(|(channel, _, _)| is_complete(channel))
+    }
+
+    /// Spin until the transfer completes, then return the result and resources
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transfer already returned its resources through `.await`.
+    pub fn wait(mut self) -> (Result<(), Error>, Channel, S, B) {
+        let (channel, _, _) = self.parts.as_ref().expect(POLLED_AFTER_COMPLETION);
+        loop {
+            if let Poll::Ready(result) = poll_status(channel) {
+                return self.finish(result);
+            }
+        }
+    }
+
+    fn finish(&mut self, result: Result<(), Error>) -> (Result<(), Error>, Channel, S, B) {
+        let (channel, mut source, buffer) = self.parts.take().expect(POLLED_AFTER_COMPLETION);
+        stop_source(&channel, &mut source);
+        self.invalidate.invalidate();
+        (result, channel, source, buffer)
+    }
+}
+
+impl<S, B> Future for Read<S, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    S: Source<B::Word>,
+{
+    type Output = (Result<(), Error>, Channel, S, B);
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let (channel, _, _) = this.parts.as_ref().expect(POLLED_AFTER_COMPLETION);
+        interrupt::register_waker(channel, cx.waker());
+        poll_status(channel).map(|result| this.finish(result))
+    }
+}
+
+impl<S, B> Unpin for Read<S, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    S: Source<B::Word>,
+{
+}
+
+impl<S, B> Drop for Read<S, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    S: Source<B::Word>,
+{
+    fn drop(&mut self) {
+        if let Some((channel, source, _)) = &mut self.parts {
+            stop_source(channel, source);
+        }
+    }
+}
+
+/// An ownership-based transfer that sends data to hardware
+///
+/// Use [`write()`] to create this transfer. The transfer's output is
+///
+/// ```text
+/// (result, channel, buffer, destination)
+/// ```
+pub struct Write<D, B>
+where
+    B: ReadBuffer,
+    B::Word: Element,
+    D: Destination<B::Word>,
+{
+    parts: Option<(Channel, B, D)>,
+}
+
+/// Use a DMA channel to send a `buffer` of data to the destination peripheral
+///
+/// The transfer starts immediately. See [`peripheral::write`]
+/// for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the destination's window size.
+pub fn write<D, B>(mut channel: Channel, buffer: B, mut destination: D) -> Write<D, B>
+where
+    B: ReadBuffer,
+    B::Word: Element,
+    D: Destination<B::Word>,
+{
+    // Safety: the buffer is valid, and stays in place, while we own it.
+    // We own it until the transfer stops.
+    let slice = unsafe { read_slice(&buffer) };
+    peripheral::prepare_write(&mut channel, slice, &mut destination);
+    // Safety: transfer prepared above.
+    unsafe { enable(&channel) };
+
+    Write {
+        parts: Some((channel, buffer, destination)),
+    }
+}
+
+impl<D, B> Write<D, B>
+where
+    B: ReadBuffer,
+    B::Word: Element,
+    D: Destination<B::Word>,
+{
+    /// Returns `true` if the transfer has completed, or produced an error
+    pub fn is_complete(&self) -> bool {
+        self.parts
+            .as_ref()
+            .is_none_or(|(channel, _, _)| is_complete(channel))
+    }
+
+    /// Spin until the transfer completes, then return the result and resources
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transfer already returned its resources through `.await`.
+    pub fn wait(mut self) -> (Result<(), Error>, Channel, B, D) {
+        let (channel, _, _) = self.parts.as_ref().expect(POLLED_AFTER_COMPLETION);
+        loop {
+            if let Poll::Ready(result) = poll_status(channel) {
+                return self.finish(result);
+            }
+        }
+    }
+
+    fn finish(&mut self, result: Result<(), Error>) -> (Result<(), Error>, Channel, B, D) {
+        let (channel, buffer, mut destination) = self.parts.take().expect(POLLED_AFTER_COMPLETION);
+        stop_destination(&channel, &mut destination);
+        (result, channel, buffer, destination)
+    }
+}
+
+impl<D, B> Future for Write<D, B>
+where
+    B: ReadBuffer,
+    B::Word: Element,
+    D: Destination<B::Word>,
+{
+    type Output = (Result<(), Error>, Channel, B, D);
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let (channel, _, _) = this.parts.as_ref().expect(POLLED_AFTER_COMPLETION);
+        interrupt::register_waker(channel, cx.waker());
+        poll_status(channel).map(|result| this.finish(result))
+    }
+}
+
+impl<D, B> Unpin for Write<D, B>
+where
+    B: ReadBuffer,
+    B::Word: Element,
+    D: Destination<B::Word>,
+{
+}
+
+impl<D, B> Drop for Write<D, B>
+where
+    B: ReadBuffer,
+    B::Word: Element,
+    D: Destination<B::Word>,
+{
+    fn drop(&mut self) {
+        if let Some((channel, _, destination)) = &mut self.parts {
+            stop_destination(channel, destination);
+        }
+    }
+}
+
+/// An ownership-based, full-duplex transfer from a single buffer
+///
+/// Use [`full_duplex()`] to create this transfer. The transfer's output is
+///
+/// ```text
+/// (result, rx_channel, tx_channel, peripheral, buffer)
+/// ```
+///
+/// If either channel produces an error, the result is the first error.
+pub struct FullDuplex<P, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    P: Bidirectional<B::Word>,
+{
+    parts: Option<(Channel, Channel, P, B)>,
+    rx_result: Option<Result<(), Error>>,
+    tx_result: Option<Result<(), Error>>,
+    invalidate: cache::Invalidate,
+}
+
+/// Perform a full-duplex DMA transfer using two DMA channels that read and
+/// write from a single buffer
+///
+/// The transfer starts immediately. See [`peripheral::full_duplex`]
+/// for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the peripheral's window sizes.
+pub fn full_duplex<P, B>(
+    mut rx_channel: Channel,
+    mut tx_channel: Channel,
+    mut peripheral: P,
+    mut buffer: B,
+) -> FullDuplex<P, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    P: Bidirectional<B::Word>,
+{
+    // Safety: the buffer is valid, and stays in place, while we own it.
+    // We own it until the transfer stops.
+    let slice = unsafe { write_slice(&mut buffer) };
+    peripheral::prepare_write(&mut tx_channel, slice, &mut peripheral);
+    peripheral::prepare_read(&mut rx_channel, &mut peripheral, slice);
+    let invalidate = cache::Invalidate::new(&rx_channel, slice);
+    // Safety: transfers prepared above.
+    unsafe {
+        enable(&rx_channel);
+        enable(&tx_channel);
+    }
+
+    FullDuplex {
+        parts: Some((rx_channel, tx_channel, peripheral, buffer)),
+        rx_result: None,
+        tx_result: None,
+        invalidate,
+    }
+}
+
+impl<P, B> FullDuplex<P, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    P: Bidirectional<B::Word>,
+{
+    /// Returns `true` if both transfers have completed, or produced an error
+    pub fn is_complete(&self) -> bool {
+        self.parts.as_ref().is_none_or(|(rx, tx, _, _)| {
+            (self.rx_result.is_some() || is_complete(rx))
+                && (self.tx_result.is_some() || is_complete(tx))
+        })
+    }
+
+    /// Spin until both transfers complete, then return the result and resources
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transfer already returned its resources through `.await`.
+    #[allow(clippy::type_complexity)]
+    pub fn wait(mut self) -> (Result<(), Error>, Channel, Channel, P, B) {
+        loop {
+            if let Poll::Ready(output) = self.poll_status() {
+                return output;
+            }
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn poll_status(&mut self) -> Poll<(Result<(), Error>, Channel, Channel, P, B)> {
+        let (rx_channel, tx_channel, _, _) = self.parts.as_ref().expect(POLLED_AFTER_COMPLETION);
+        if self.rx_result.is_none() {
+            self.rx_result = match poll_status(rx_channel) {
+                Poll::Ready(result) => Some(result),
+                Poll::Pending => None,
+            };
+        }
+        if self.tx_result.is_none() {
+            self.tx_result = match poll_status(tx_channel) {
+                Poll::Ready(result) => Some(result),
+                Poll::Pending => None,
+            };
+        }
+
+        let result = match (self.rx_result, self.tx_result) {
+            (Some(Err(err)), _) | (_, Some(Err(err))) => Err(err),
+            (Some(Ok(())), Some(Ok(()))) => Ok(()),
+            _ => return Poll::Pending,
+        };
+
+        let (rx_channel, tx_channel, mut peripheral, buffer) =
+            self.parts.take().expect(POLLED_AFTER_COMPLETION);
+        stop_destination(&tx_channel, &mut peripheral);
+        stop_source(&rx_channel, &mut peripheral);
+        self.invalidate.invalidate();
+        Poll::Ready((result, rx_channel, tx_channel, peripheral, buffer))
+    }
+}
+
+impl<P, B> Future for FullDuplex<P, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    P: Bidirectional<B::Word>,
+{
+    type Output = (Result<(), Error>, Channel, Channel, P, B);
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let (rx_channel, tx_channel, _, _) = this.parts.as_ref().expect(POLLED_AFTER_COMPLETION);
+        interrupt::register_waker(rx_channel, cx.waker());
+        interrupt::register_waker(tx_channel, cx.waker());
+        this.poll_status()
+    }
+}
+
+impl<P, B> Unpin for FullDuplex<P, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    P: Bidirectional<B::Word>,
+{
+}
+
+impl<P, B> Drop for FullDuplex<P, B>
+where
+    B: WriteBuffer,
+    B::Word: Element,
+    P: Bidirectional<B::Word>,
+{
+    fn drop(&mut self) {
+        if let Some((rx_channel, tx_channel, peripheral, _)) = &mut self.parts {
+            stop_destination(tx_channel, peripheral);
+            stop_source(rx_channel, peripheral);
+        }
+    }
+}
+
+/// Returns `true` if the channel is done, or in error
+fn is_complete(channel: &Channel) -> bool {
+    channel.is_complete() || channel.is_error()
+}
+
+/// Stop a peripheral-to-memory transfer
+fn stop_source<S, E>(channel: &Channel, source: &mut S)
+where
+    S: Source<E>,
+    E: Element,
+{
+    source.disable_source();
+    while channel.is_hardware_signaling() {}
+    stop(channel);
+}
+
+/// Stop a memory-to-peripheral transfer
+fn stop_destination<D, E>(channel: &Channel, destination: &mut D)
+where
+    D: Destination<E>,
+    E: Element,
+{
+    destination.disable_destination();
+    while channel.is_hardware_signaling() {}
+    stop(channel);
+}
@@ -0,0 +1,149 @@
+//! Split DMA channels between cores
+
+use crate::{channel::Channel, Callback, Dma};
+
+/// A view of a [`Dma`] that's restricted to a set of channels
+///
+/// On a multi-core chip, like the i.MX RT 1170, more than one core may program
+/// the same DMA controller. Give each core a `Partition` with a disjoint channel
+/// mask. A partition
+///
+/// - only allocates channels in its mask. Allocating any other channel panics.
+/// - only services its own channels in [`on_interrupt`](Partition::on_interrupt)
+///   and [`on_interrupt_all`](Partition::on_interrupt_all).
+///
+/// Bit `n` of the mask represents DMA channel `n`. Define the masks in code
+/// that's shared by every core's firmware, and check that they're disjoint
+/// with [`is_disjoint`](Partition::is_disjoint).
+///
+/// A partition has the same interrupt methods as a `Dma`, so you can use it
+/// with [`bind_interrupts!`](crate::bind_interrupts).
+///
+/// ```
+/// use imxrt_dma::{Dma, Partition};
+///
+/// // Shared by both cores' firmware.
+/// const CM7_CHANNELS: u32 = 0x0000_FFFF;
+/// const CM4_CHANNELS: u32 = 0xFFFF_0000;
+///
+/// static DMA: Dma<32> = // Handle to DMA driver.
+///     # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+/// // In the CM7 firmware:
+/// static CM7_DMA: Partition<32> = Partition::new(&DMA, CM7_CHANNELS);
+/// const _: () = assert!(CM7_DMA.is_disjoint(CM4_CHANNELS));
+///
+/// // Safety: we only allocate one DMA channel 7 object.
+/// let channel = unsafe { CM7_DMA.channel(7) };
+/// assert!(!CM7_DMA.contains(23));
+/// ```
+pub struct Partition<const CHANNELS: usize> {
+    dma: &'static Dma<CHANNELS>,
+    mask: u32,
+}
+
+impl<const CHANNELS: usize> Partition<CHANNELS> {
+    /// Create a partition of `dma` that owns the channels in `mask`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mask` includes a channel that's greater than or equal to
+    /// the maximum number of channels.
+    pub const fn new(dma: &'static Dma<CHANNELS>, mask: u32) -> Self {
+        assert!(CHANNELS >= 32 || mask >> CHANNELS == 0);
+        Partition { dma, mask }
+    }
+
+    /// Returns the partition's channel mask
+    pub const fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Returns the DMA driver that's partitioned
+    pub(crate) const fn dma(&self) -> &'static Dma<CHANNELS> {
+        self.dma
+    }
+
+    /// Returns `true` if the partition owns `channel`
+    pub const fn contains(&self, channel: usize) -> bool {
+        channel < CHANNELS && self.mask & (1 << channel) != 0
+    }
+
+    /// Returns `true` if the partition doesn't own any of the channels in `mask`
+    pub const fn is_disjoint(&self, mask: u32) -> bool {
+        self.mask & mask == 0
+    }
+
+    /// Creates the DMA channel described by `index`
+    ///
+    /// # Safety
+    ///
+    /// See [`Dma::channel`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the partition doesn't own channel `index`.
+    pub unsafe fn channel(&'static self, index: usize) -> Channel {
+        assert!(self.contains(index), "DMA channel isn't in this partition");
+        self.dma.channel(index)
+    }
+
+    /// Creates the DMA channel described by `index`, if the partition owns
+    /// that channel
+    ///
+    /// # Safety
+    ///
+    /// See [`Dma::channel`].
+    pub unsafe fn try_channel(&'static self, index: usize) -> Option<Channel> {
+        self.contains(index).then(|| self.dma.channel(index))
+    }
+
+    /// Handle a DMA interrupt for `channel`, if the partition owns the channel
+    ///
+    /// If the partition doesn't own `channel`, this does nothing. Another core
+    /// is expected to handle the interrupt.
+    ///
+    /// # Safety
+    ///
+    /// See [`Dma::on_interrupt`].
+    #[inline(always)]
+    pub unsafe fn on_interrupt(&'static self, channel: usize) {
+        if self.contains(channel) {
+            self.dma.on_interrupt(channel);
+        }
+    }
+
+    /// Handle a DMA interrupt for every channel in the partition that needs it
+    ///
+    /// Channels outside of the partition are not touched.
+    ///
+    /// # Safety
+    ///
+    /// See [`Dma::on_interrupt_all`].
+    pub unsafe fn on_interrupt_all(&'static self) {
+        self.dma.on_interrupt_mask(self.mask);
+    }
+
+    /// Cancel the transfer that's using `channel`
+    ///
+    /// See [`Dma::cancel`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the partition doesn't own `channel`.
+    pub fn cancel(&'static self, channel: usize) {
+        assert!(self.contains(channel));
+        self.dma.cancel(channel);
+    }
+
+    /// Set the callback for `channel`
+    ///
+    /// See [`Dma::set_callback`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the partition doesn't own `channel`.
+    pub fn set_callback(&'static self, channel: usize, callback: Option<Callback>) {
+        assert!(self.contains(channel));
+        self.dma.set_callback(channel, callback);
+    }
+}
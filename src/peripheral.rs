@@ -6,13 +6,19 @@
 //! Each future documents when it resolves. To wake the executor, you can
 //! route the DMA channel's interrupt handler to [`on_interrupt()`](crate::Dma::on_interrupt).
 //! Otherwise, you can poll the future in a loop.
+//!
+//! If your buffers are in cacheable memory, enable the channel's
+//! [cache maintenance](crate::channel::Channel::set_cache_maintenance).
 
 use super::{
-    channel::{self, Channel, Configuration},
+    cache,
+    channel::{self, Channel, Configuration, Descriptor},
+    interrupt::suspend_methods,
     Element, Error, Transfer,
 };
 
 use core::{
+    cell::Cell,
     future::Future,
     marker::PhantomData,
     pin::Pin,
@@ -45,7 +51,20 @@ pub unsafe trait Source<E: Element> {
     ///
     /// This memory is assumed to be static. Repeated `source` calls
     /// should always return the same address.
+    ///
+    /// If the peripheral exposes a [`Window`] of registers, this is the
+    /// address of the first register in the window.
     fn source_address(&self) -> *const E;
+    /// Returns the window of registers that the DMA channel reads for
+    /// each service request
+    ///
+    /// The default implementation describes the single register at
+    /// `source_address`. Override this if the peripheral needs each
+    /// request to drain a group of registers, like a set of FlexIO
+    /// shifter buffers.
+    fn source_window(&self) -> Window {
+        Window::single::<E>()
+    }
     /// Perform any actions necessary to enable DMA transfers
     ///
     /// Callers use this method to put the peripheral in a state where
@@ -80,7 +99,20 @@ pub unsafe trait Destination<E: Element> {
     /// This is the register that software writes to when sending data to a
     /// device. The type of the pointer describes the type of reads the
     /// DMA channel performs when transferring data.
+    ///
+    /// If the peripheral exposes a [`Window`] of registers, this is the
+    /// address of the first register in the window.
     fn destination_address(&self) -> *const E;
+    /// Returns the window of registers that the DMA channel writes for
+    /// each service request
+    ///
+    /// The default implementation describes the single register at
+    /// `destination_address`. Override this if the peripheral needs each
+    /// request to fill a group of registers, like a set of FlexIO
+    /// shifter buffers.
+    fn destination_window(&self) -> Window {
+        Window::single::<E>()
+    }
     /// Perform any actions necessary to enable DMA transfers
     ///
     /// Callers use this method to put the peripheral into a state where
@@ -92,6 +124,169 @@ pub unsafe trait Destination<E: Element> {
     fn disable_destination(&mut self);
 }
 
+/// A source that can signal the end of incoming data by detecting an idle line
+///
+/// Serial peripherals, like UARTs, can detect when the receive line stays idle
+/// after receiving data. An `IdleSource` lets a DMA transfer end early when the
+/// peripheral detects that condition, instead of waiting for a full buffer.
+///
+/// # Safety
+///
+/// `IdleSource` has the same safety requirements as [`Source`].
+pub unsafe trait IdleSource<E: Element>: Source<E> {
+    /// Poll for an idle line
+    ///
+    /// Return `Poll::Ready` if the peripheral detected an idle line after
+    /// receiving data. Otherwise, arrange for `cx`'s waker to be woken
+    /// when the peripheral detects the idle line, and return `Poll::Pending`.
+    ///
+    /// [`enable_source`](Source::enable_source) should clear any idle condition
+    /// from a previous transfer.
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<()>;
+}
+
+/// A group of peripheral registers serviced by a single DMA request
+///
+/// By default, a [`Source`] or [`Destination`] exposes one register, and each
+/// service request moves one element. A `Window` describes a peripheral that
+/// needs each request to move a group of registers. Each register is `width`
+/// bytes wide. The DMA channel steps through the window by `stride` bytes, then
+/// wraps back to the first register using the address `modulo`.
+///
+/// A window's width must match the element type `E` of the peripheral trait.
+///
+/// ```
+/// use imxrt_dma::peripheral::Window;
+///
+/// // Four contiguous, 32-bit shifter buffers.
+/// const SHIFTBUF: Window = Window::contiguous::<u32>(4);
+/// assert_eq!(SHIFTBUF.registers(), 4);
+/// assert_eq!(SHIFTBUF.width(), 4);
+/// assert_eq!(SHIFTBUF.stride(), 4);
+/// assert_eq!(SHIFTBUF.modulo(), 4);
+/// assert_eq!(SHIFTBUF.minor_loop_bytes(), 16);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    registers: u16,
+    width: u8,
+    stride: i16,
+    modulo: u8,
+}
+
+impl Window {
+    /// Describe a window of `registers`, each `width` bytes wide and `stride` bytes apart
+    ///
+    /// `modulo` is the address modulo (see TCD\[SMOD\] and TCD\[DMOD\]) that
+    /// wraps the address back to the first register. If `stride` is zero, the
+    /// DMA channel accesses the same register `registers` times per request, and
+    /// `modulo` must be zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if
+    ///
+    /// - `registers` is zero
+    /// - `width` is not 1, 2, 4, or 8 bytes
+    /// - the window has one register, but `stride` is not zero
+    /// - `stride` is zero, but `modulo` is not zero
+    /// - the window steps through memory, but `2^modulo` does not equal the
+    ///   number of bytes spanned by the window
+    pub const fn new(registers: u16, width: u8, stride: i16, modulo: u8) -> Self {
+        assert!(registers > 0, "DMA register window is empty");
+        assert!(
+            matches!(width, 1 | 2 | 4 | 8),
+            "DMA register window width is not 1, 2, 4, or 8 bytes"
+        );
+        assert!(
+            registers > 1 || stride == 0,
+            "DMA register window of one register has a stride"
+        );
+        assert!(
+            stride != 0 || modulo == 0,
+            "DMA register window without a stride has a modulo"
+        );
+        if stride != 0 {
+            assert!(
+                modulo < 32 && (registers as u32) * (stride.unsigned_abs() as u32) == 1 << modulo,
+                "DMA register window modulo does not span the window"
+            );
+        }
+        Window {
+            registers,
+            width,
+            stride,
+            modulo,
+        }
+    }
+
+    /// Describe the single register of type `E`
+    ///
+    /// This is the default window for all peripherals.
+    pub const fn single<E: Element>() -> Self {
+        Self::new(1, core::mem::size_of::<E>() as u8, 0, 0)
+    }
+
+    /// Describe a window of `registers` contiguous registers of type `E`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `registers` is zero or not a power of two.
+    pub const fn contiguous<E: Element>(registers: u16) -> Self {
+        assert!(
+            registers.is_power_of_two(),
+            "DMA register window size is not a power of two"
+        );
+        let width = core::mem::size_of::<E>() as u8;
+        if registers == 1 {
+            return Self::single::<E>();
+        }
+        let span = (registers as u32) * (width as u32);
+        Self::new(registers, width, width as i16, span.trailing_zeros() as u8)
+    }
+
+    /// Returns the number of registers in the window
+    pub const fn registers(&self) -> u16 {
+        self.registers
+    }
+
+    /// Returns the width, in bytes, of each register in the window
+    pub const fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// Returns the offset, in bytes, between registers in the window
+    pub const fn stride(&self) -> i16 {
+        self.stride
+    }
+
+    /// Returns the address modulo that wraps back to the first register
+    pub const fn modulo(&self) -> u8 {
+        self.modulo
+    }
+
+    /// Returns the number of bytes moved by one service request
+    ///
+    /// Use this as the minor loop size when accessing the window.
+    pub const fn minor_loop_bytes(&self) -> u32 {
+        (self.registers as u32) * (self.width as u32)
+    }
+
+    /// Returns the number of service requests needed to move `len` elements
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is not a multiple of the window size.
+    fn requests(&self, len: usize) -> u16 {
+        let registers = self.registers as usize;
+        assert!(
+            len.is_multiple_of(registers),
+            "DMA buffer length is not a multiple of the register window"
+        );
+        (len / registers) as u16
+    }
+}
+
 /// A DMA transfer that receives data from hardware
 ///
 /// The future resolves when the peripheral has provided all
@@ -105,9 +300,39 @@ where
     channel: &'a Channel,
     source: &'a mut S,
     transfer: Transfer<'a>,
+    _cache: cache::Invalidate,
+    /// Cleared when a prepared read completes.
+    dirty: Option<&'a Cell<bool>>,
     _elem: PhantomData<&'a mut E>,
 }
 
+impl<'a, S, E> Read<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Returns the DMA channel used by this transfer
+    pub(crate) fn channel(&self) -> &'a Channel {
+        self.channel
+    }
+
+    suspend_methods!("read");
+
+    /// Returns the source peripheral
+    fn source(self: Pin<&mut Self>) -> &mut S {
+        // Safety: the source is never pinned.
+        unsafe { self.get_unchecked_mut().source }
+    }
+
+    /// Invalidate the buffer now, instead of when the read drops
+    ///
+    /// Call this once the channel is stopped.
+    fn invalidate(self: Pin<&mut Self>) {
+        // Safety: the cache guard is never pinned.
+        unsafe { self.get_unchecked_mut() }._cache.invalidate();
+    }
+}
+
 impl<S, E> Future for Read<'_, S, E>
 where
     S: Source<E>,
@@ -115,8 +340,13 @@ where
 {
     type Output = Result<(), Error>;
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let dirty = self.dirty;
         // Safety: no movement from transfer future...
-        unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)
+        let poll = unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx);
+        if let (Poll::Ready(Ok(())), Some(dirty)) = (&poll, dirty) {
+            dirty.set(false);
+        }
+        poll
     }
 }
 
@@ -127,45 +357,521 @@ where
 {
     fn drop(&mut self) {
         self.source.disable_source();
-        while self.channel.is_hardware_signaling() {}
+        while self.channel.is_enabled() && self.channel.is_hardware_signaling() {}
         // Drop `transfer` to finish cancellation...
     }
 }
 
-fn prepare_read<S, E>(channel: &mut Channel, source: &mut S, buffer: &mut [E])
+pub(crate) fn prepare_read<S, E>(channel: &mut Channel, source: &mut S, buffer: &mut [E])
 where
     S: Source<E>,
     E: Element,
 {
-    channel.disable();
-
-    channel.set_disable_on_completion(true);
-    channel.set_channel_configuration(Configuration::enable(source.source_signal()));
     // Safety: hardware source address must be valid, otherwise impl is unsound.
     // Destination buffer lifetime captured by future. The combination of minor
     // loops and transfer iterations ensure that we do not exceed the end of the
     // destination.
+    cache::prepare_destination(channel, buffer);
     unsafe {
-        channel::set_source_hardware(channel, source.source_address());
+        prepare_source(channel, source, buffer.len());
         channel::set_destination_linear_buffer(channel, buffer);
-        channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+    }
+
+    source.enable_source();
+}
+
+/// Prepare a read of `len` elements that are all written to `sink`
+///
+/// The caller is responsible for keeping `sink` borrowed for the duration
+/// of the transfer.
+#[cfg(feature = "embedded-hal-async")]
+pub(crate) fn prepare_read_discard<S, E>(
+    channel: &mut Channel,
+    source: &mut S,
+    sink: &mut E,
+    len: usize,
+) where
+    S: Source<E>,
+    E: Element,
+{
+    // Safety: see prepare_read. The sink is a single element that's
+    // never incremented.
+    unsafe {
+        prepare_source(channel, source, len);
+        channel::set_destination_hardware(channel, sink);
+    }
+
+    source.enable_source();
+}
+
+/// Configure the source side of a peripheral-to-memory transfer
+///
+/// # Safety
+///
+/// Caller must configure a destination that can receive `len` elements.
+unsafe fn prepare_source<S, E>(channel: &mut Channel, source: &mut S, len: usize)
+where
+    S: Source<E>,
+    E: Element,
+{
+    channel.disable();
+
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+    let window = source.source_window();
+    channel::set_source_hardware_window(channel, source.source_address(), &window);
+    channel.set_minor_loop_bytes(window.minor_loop_bytes());
+    channel.set_transfer_iterations(window.requests(len));
+}
+
+/// Use a DMA channel to receive a `buffer` of elements from the source peripheral.
+///
+/// Each service request reads the source's full [`Window`] of registers.
+///
+/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
+/// to wake the executor when the transfer completes. Otherwise, poll the future.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the source's window size.
+///
+/// # Example
+///
+/// Receive 32 bytes from a LPUART peripheral. Wake the executor when the transfer completes.
+///
+/// ```no_run
+/// use imxrt_dma::{peripheral, channel::Channel};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+///
+/// // #[cortex_m_rt::interrupt]
+/// fn DMA7() {
+///     // Safety: DMA channel 7 valid and used by a future.
+///     unsafe { DMA.on_interrupt(7) };
+/// }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut lpuart = // A LPUART peripheral
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// channel_7.set_interrupt_on_completion(true);
+/// // TODO unmask interrupts in NVIC!
+///
+/// let mut buffer = [0u8; 32];
+///
+/// peripheral::read(
+///     &mut channel_7,
+///     &mut lpuart,
+///     &mut buffer,
+/// ).await?;
+/// # Ok(()) }
+/// ```
+pub fn read<'a, S, E>(
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [E],
+) -> Read<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    prepare_read(channel, source, buffer);
+    Read {
+        channel,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        dirty: None,
+        _cache: cache::Invalidate::new(channel, buffer),
+        source,
+        _elem: PhantomData,
+    }
+}
+
+/// Use a DMA channel to receive a `buffer` of elements that differ in width
+/// from the source peripheral's elements
+///
+/// `P` is the width of the source register, and `M` is the width of the
+/// elements in memory.
+///
+/// - If `M` is narrower than `P`, each service request reads the low bytes
+///   of the register, and stores one `M`. The channel uses `M`-wide reads, so
+///   the peripheral must accept narrow reads of the register.
+/// - If `M` is wider than `P`, each service request reads the register
+///   `size_of::<M>() / size_of::<P>()` times, and packs the data into one `M`.
+///
+/// Otherwise, this behaves like [`read`](fn@read).
+///
+/// # Example
+///
+/// Read a 32-bit FIFO register, but store only the low byte.
+///
+/// ```no_run
+/// use imxrt_dma::peripheral;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u32> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u32 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let mut fifo = // A DMA-capable peripheral with a 32-bit FIFO
+///     # X;
+/// let mut bytes = [0u8; 16];
+///
+/// peripheral::read_mixed(&mut channel, &mut fifo, &mut bytes).await?;
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the buffer is empty, or if the source describes a [`Window`] of
+/// more than one register.
+pub fn read_mixed<'a, S, P, M>(
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [M],
+) -> Read<'a, S, P>
+where
+    S: Source<P>,
+    P: Element,
+    M: Element,
+{
+    assert!(
+        source.source_window() == Window::single::<P>(),
+        "DMA mixed-width transfers need a single register"
+    );
+    assert!(!buffer.is_empty(), "DMA buffer is empty");
+    cache::prepare_destination(channel, buffer);
+    // Safety: see prepare_read. Each service request stores one M, and
+    // there's one service request per element of the buffer.
+    unsafe {
+        channel.disable();
+        channel.set_disable_on_completion(true);
+        channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+        if core::mem::size_of::<M>() < core::mem::size_of::<P>() {
+            // Little endian: the low bytes are at the register address.
+            channel::set_source_hardware(channel, source.source_address().cast::<M>());
+        } else {
+            channel::set_source_hardware(channel, source.source_address());
+        }
+        channel::set_destination_linear_buffer(channel, buffer);
+        channel.set_minor_loop_bytes(core::mem::size_of::<M>() as u32);
         channel.set_transfer_iterations(buffer.len() as u16);
     }
+    source.enable_source();
+
+    Read {
+        channel,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        dirty: None,
+        _cache: cache::Invalidate::new(channel, buffer),
+        source,
+        _elem: PhantomData,
+    }
+}
+
+/// A DMA transfer that receives data from hardware into many buffers
+///
+/// The future resolves when the peripheral has filled all buffers. Use
+/// [`read_vectored()`] to construct this future.
+pub struct ReadVectored<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    channel: &'a Channel,
+    source: &'a mut S,
+    transfer: Transfer<'a>,
+    buffers: &'a mut [&'a mut [E]],
+}
+
+impl<S, E> ReadVectored<'_, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    suspend_methods!("read");
+}
+
+impl<S, E> Future for ReadVectored<'_, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: no movement from transfer future...
+        unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)
+    }
+}
+
+impl<S, E> Drop for ReadVectored<'_, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        self.source.disable_source();
+        while self.channel.is_enabled() && self.channel.is_hardware_signaling() {}
+        if self.channel.cache_maintenance() {
+            self.channel.disable();
+            while self.channel.is_active() {}
+            for buffer in self.buffers.iter_mut() {
+                cache::invalidate(buffer);
+            }
+        }
+        // Drop `transfer` to finish cancellation...
+    }
+}
+
+/// Use a DMA channel to receive data from the source peripheral into many `buffers`
+///
+/// The channel fills each buffer in turn, then resolves once. This behaves like
+/// [`read`](fn@read), except that the data lands in separate buffers. The transfer
+/// skips empty buffers.
+///
+/// Supply one [`Descriptor`] for each non-empty buffer. The transfer borrows the
+/// descriptors until it's dropped.
+///
+/// # Example
+///
+/// Receive a header and a payload into separate buffers.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Descriptor, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let mut uart = // A DMA-capable UART
+///     # X;
+/// let mut header = [0u8; 4];
+/// let mut payload = [0u8; 32];
+/// let mut descriptors = [Descriptor::new(); 2];
+///
+/// peripheral::read_vectored(
+///     &mut channel,
+///     &mut uart,
+///     &mut [&mut header, &mut payload],
+///     &mut descriptors,
+/// )
+/// .await?;
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if
+///
+/// - all buffers are empty
+/// - there are fewer descriptors than non-empty buffers
+/// - a buffer length is not a multiple of the source's window size
+pub fn read_vectored<'a, S, E>(
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffers: &'a mut [&'a mut [E]],
+    descriptors: &'a mut [Descriptor],
+) -> ReadVectored<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    let descriptors = vectored_descriptors(descriptors, buffers.iter().map(|b| b.len()));
+    let window = source.source_window();
+    // Safety: see prepare_read. The first buffer describes the template
+    // for all descriptors. All buffers and descriptors are borrowed for 'a.
+    unsafe {
+        let first = buffers.iter_mut().find(|b| !b.is_empty()).unwrap();
+        prepare_source(channel, source, first.len());
+        channel::set_destination_linear_buffer(channel, first);
+        let template = channel.descriptor();
+        let nonempty = buffers.iter_mut().filter(|b| !b.is_empty());
+        for (descriptor, buffer) in descriptors.iter_mut().zip(nonempty) {
+            cache::prepare_destination(channel, buffer);
+            *descriptor = template;
+            descriptor.set_destination(buffer.as_ptr(), buffer.len());
+            descriptor.set_transfer_iterations(window.requests(buffer.len()));
+        }
+        channel::set_scatter_gather(channel, descriptors, false);
+    }
+
+    source.enable_source();
+    ReadVectored {
+        channel,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        source,
+        buffers,
+    }
+}
+
+/// Returns enough descriptors for each non-empty buffer
+///
+/// # Panics
+///
+/// Panics if there are no non-empty buffers, or if there
+/// are too few descriptors.
+fn vectored_descriptors(
+    descriptors: &mut [Descriptor],
+    lengths: impl Iterator<Item = usize>,
+) -> &mut [Descriptor] {
+    let count = lengths.filter(|&len| len > 0).count();
+    assert!(count > 0, "DMA vectored transfer has no data");
+    assert!(
+        descriptors.len() >= count,
+        "DMA vectored transfer needs {count} descriptors"
+    );
+    &mut descriptors[..count]
+}
+
+/// A peripheral-to-memory transfer that's programmed once, and run many times
+///
+/// [`new`](PreparedRead::new) programs the DMA channel. Each [`run`](PreparedRead::run)
+/// only enables the peripheral, and returns a fresh [`Read`] future. Use a
+/// prepared read when you repeat the same transfer in a tight loop.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::peripheral::PreparedRead;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl imxrt_dma::peripheral::Source<u16> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u16 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+/// # fn control(_: &[u16]) {}
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let mut adc = // A DMA-capable ADC
+///     # X;
+/// let mut samples = [0u16; 8];
+///
+/// let mut prepared = PreparedRead::new(&mut channel, &mut adc, &mut samples);
+/// loop {
+///     prepared.run().await?;
+///     control(prepared.buffer());
+/// }
+/// # }
+/// ```
+pub struct PreparedRead<'a, S, E> {
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [E],
+    /// The programmed transfer, restored before a run if needed.
+    descriptor: Descriptor,
+    /// Set while a run is incomplete, or if it didn't complete.
+    dirty: Cell<bool>,
+}
+
+impl<'a, S, E> PreparedRead<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Program `channel` to receive a `buffer` of elements from the source peripheral
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer length is not a multiple of the source's window size.
+    pub fn new(channel: &'a mut Channel, source: &'a mut S, buffer: &'a mut [E]) -> Self {
+        // Safety: see prepare_read. The buffer is borrowed for 'a.
+        unsafe {
+            prepare_source(channel, source, buffer.len());
+            channel::set_destination_linear_buffer(channel, buffer);
+        }
+        PreparedRead {
+            descriptor: channel.descriptor(),
+            dirty: Cell::new(false),
+            channel,
+            source,
+            buffer,
+        }
+    }
+
+    /// Run the transfer
+    ///
+    /// The returned future behaves like the future returned from [`read`].
+    /// Each run starts from the beginning of the buffer. If the previous run
+    /// didn't complete, the run first restores the programmed transfer.
+    pub fn run(&mut self) -> Read<'_, S, E> {
+        if self.dirty.replace(true) {
+            // Safety: the descriptor was programmed by new, and the buffer
+            // is borrowed for 'a.
+            unsafe { self.channel.set_descriptor(&self.descriptor) };
+        }
+        cache::prepare_destination(self.channel, self.buffer);
+        self.source.enable_source();
+        let channel: &Channel = self.channel;
+        Read {
+            channel,
+            // Safety: transfer is correctly defined
+            transfer: unsafe { Transfer::new(channel) },
+            dirty: Some(&self.dirty),
+            _cache: cache::Invalidate::new(channel, self.buffer),
+            source: self.source,
+            _elem: PhantomData,
+        }
+    }
 
-    source.enable_source();
+    /// Returns the buffer
+    pub fn buffer(&mut self) -> &mut [E] {
+        self.buffer
+    }
 }
 
-/// Use a DMA channel to receive a `buffer` of elements from the source peripheral.
+/// Use a DMA channel to receive up to a `buffer` of elements from the source peripheral
 ///
-/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
-/// to wake the executor when the transfer completes. Otherwise, poll the future.
+/// The future resolves with the received prefix of `buffer`. The transfer ends when
+///
+/// - the buffer is full.
+/// - the peripheral detects an idle line. See [`IdleSource`].
+/// - someone cancels the transfer with [`Dma::cancel`](crate::Dma::cancel).
+///
+/// The length of the prefix is computed from the channel's transfer iterations.
+/// If the peripheral detects an idle line before the channel receives any
+/// elements, the transfer keeps waiting for data. A cancellation may resolve
+/// with an empty prefix.
+///
+/// Like [`read`], each service request reads the source's full [`Window`] of
+/// registers. The prefix is always a multiple of the window size.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the source's window size.
 ///
 /// # Example
 ///
-/// Receive 32 bytes from a LPUART peripheral. Wake the executor when the transfer completes.
+/// Receive a frame of at most 64 bytes from a LPUART peripheral.
 ///
 /// ```no_run
-/// use imxrt_dma::{peripheral, channel::Channel};
+/// use imxrt_dma::peripheral;
+/// # use core::task::{Context, Poll};
 /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
 /// # struct X;
 /// # unsafe impl peripheral::Source<u8> for X {
@@ -174,46 +880,150 @@ where
 /// #   fn enable_source(&mut self) { panic!() }
 /// #   fn disable_source(&mut self) { panic!() }
 /// # }
-///
-/// // #[cortex_m_rt::interrupt]
-/// fn DMA7() {
-///     // Safety: DMA channel 7 valid and used by a future.
-///     unsafe { DMA.on_interrupt(7) };
-/// }
+/// # unsafe impl peripheral::IdleSource<u8> for X {
+/// #   fn poll_idle(&mut self, _: &mut Context<'_>) -> Poll<()> { panic!() }
+/// # }
 ///
 /// # async fn f() -> imxrt_dma::Result<()> {
-/// let mut lpuart = // A LPUART peripheral
+/// let mut lpuart = // A LPUART receiver
 ///     # X;
-/// let mut channel_7: Channel = // DMA channel 7
+/// let mut channel = // DMA channel 7
 ///     # unsafe { DMA.channel(7) };
-/// channel_7.set_interrupt_on_completion(true);
-/// // TODO unmask interrupts in NVIC!
-///
-/// let mut buffer = [0u8; 32];
+/// channel.set_interrupt_on_completion(true);
 ///
-/// peripheral::read(
-///     &mut channel_7,
-///     &mut lpuart,
-///     &mut buffer,
-/// ).await?;
+/// let mut buffer = [0u8; 64];
+/// let frame = peripheral::receive(&mut channel, &mut lpuart, &mut buffer).await?;
 /// # Ok(()) }
 /// ```
-pub fn read<'a, S, E>(
+pub fn receive<'a, S, E>(
     channel: &'a mut Channel,
     source: &'a mut S,
     buffer: &'a mut [E],
-) -> Read<'a, S, E>
+) -> Receive<'a, S, E>
+where
+    S: IdleSource<E>,
+    E: Element,
+{
+    let registers = source.source_window().registers() as usize;
+    let buffer: *mut [E] = buffer;
+    Receive {
+        // Safety: the buffer is borrowed for 'a. The reborrow ends once the
+        // read is programmed; the read doesn't keep a reference.
+        read: read(channel, source, unsafe { &mut *buffer }),
+        buffer: Some(buffer),
+        registers,
+        _buffer: PhantomData,
+    }
+}
+
+/// A DMA transfer that receives a variable amount of data from hardware
+///
+/// Use [`receive()`] to construct this future. Once the future resolves,
+/// polling it again returns `Pending`; it never restarts the transfer.
+pub struct Receive<'a, S, E>
 where
     S: Source<E>,
     E: Element,
 {
-    prepare_read(channel, source, buffer);
-    Read {
-        channel,
-        // Safety: transfer is correctly defined
-        transfer: unsafe { Transfer::new(channel) },
-        source,
-        _elem: PhantomData,
+    read: Read<'a, S, E>,
+    /// The buffer written by `read`, or `None` once the future resolves.
+    buffer: Option<*mut [E]>,
+    /// Elements moved per minor loop.
+    registers: usize,
+    _buffer: PhantomData<&'a mut [E]>,
+}
+
+impl<'a, S, E> Receive<'a, S, E>
+where
+    S: IdleSource<E>,
+    E: Element,
+{
+    /// Returns the number of elements received by an incomplete transfer
+    fn received(&self) -> usize {
+        let channel = self.read.channel();
+        let iterations = channel
+            .beginning_transfer_iterations()
+            .saturating_sub(channel.current_transfer_iterations());
+        iterations as usize * self.registers
+    }
+
+    /// Returns the first `len` elements of the buffer
+    ///
+    /// Call this once the channel is stopped.
+    fn prefix(self: Pin<&mut Self>, len: usize) -> Poll<Result<&'a mut [E], Error>> {
+        // Safety: pin projection OK, read is never moved, and the
+        // buffer is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        unsafe { Pin::new_unchecked(&mut this.read) }.invalidate();
+        let buffer = this.buffer.take().expect("receive has a buffer");
+        // Safety: the channel is stopped, so the DMA controller no longer
+        // writes the buffer. The buffer is borrowed for 'a, and this is the
+        // only reference to it.
+        Poll::Ready(Ok(unsafe {
+            core::slice::from_raw_parts_mut(buffer.cast::<E>(), len)
+        }))
+    }
+}
+
+impl<'a, S, E> Future for Receive<'a, S, E>
+where
+    S: IdleSource<E>,
+    E: Element,
+{
+    type Output = Result<&'a mut [E], Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let len = match &self.buffer {
+            Some(buffer) => buffer.len(),
+            // Already resolved. Don't poll the read, which would re-enable
+            // the channel.
+            None => return Poll::Pending,
+        };
+
+        // Safety: pin projection OK, read is never moved.
+        let mut read = unsafe { self.as_mut().map_unchecked_mut(|this| &mut this.read) };
+        let channel = read.channel();
+
+        match read.as_mut().poll(cx) {
+            Poll::Ready(Ok(())) => return self.prefix(len),
+            Poll::Ready(Err(err)) if err.is_cancelled() => {
+                // The transfer may have completed before the cancellation.
+                // Then, the iterations have already reloaded.
+                let received = if channel.is_complete() {
+                    len
+                } else {
+                    self.received()
+                };
+                return self.prefix(received);
+            }
+            Poll::Ready(Err(err)) => {
+                // Safety: the buffer is never pinned.
+                unsafe { self.get_unchecked_mut() }.buffer = None;
+                return Poll::Ready(Err(err));
+            }
+            Poll::Pending => {}
+        }
+
+        if read.as_mut().source().poll_idle(cx).is_ready() {
+            // Let the DMA channel service any pending request, then stop it.
+            while channel.is_enabled() && channel.is_hardware_signaling() {}
+            channel.disable();
+            while channel.is_active() {}
+
+            if channel.is_complete() {
+                return self.prefix(len);
+            }
+            match self.received() {
+                // Nothing received; keep waiting for data.
+                //
+                // Safety: the transfer is still valid. Re-enabling from where
+                // it stopped.
+                0 => unsafe { channel.enable() },
+                received => return self.prefix(received),
+            }
+        }
+
+        Poll::Pending
     }
 }
 
@@ -229,9 +1039,24 @@ where
     channel: &'a Channel,
     destination: &'a mut D,
     transfer: Transfer<'a>,
+    /// Cleared when a prepared write completes.
+    dirty: Option<&'a Cell<bool>>,
     _elem: PhantomData<&'a E>,
 }
 
+impl<'a, D, E> Write<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Returns the DMA channel used by this transfer
+    pub(crate) fn channel(&self) -> &'a Channel {
+        self.channel
+    }
+
+    suspend_methods!("write");
+}
+
 impl<D, E> Future for Write<'_, D, E>
 where
     D: Destination<E>,
@@ -239,8 +1064,13 @@ where
 {
     type Output = Result<(), Error>;
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let dirty = self.dirty;
         // Safety: no movement from transfer future...
-        unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)
+        let poll = unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx);
+        if let (Poll::Ready(Ok(())), Some(dirty)) = (&poll, dirty) {
+            dirty.set(false);
+        }
+        poll
     }
 }
 
@@ -251,38 +1081,84 @@ where
 {
     fn drop(&mut self) {
         self.destination.disable_destination();
-        while self.channel.is_hardware_signaling() {}
+        while self.channel.is_enabled() && self.channel.is_hardware_signaling() {}
         // Drop `transfer` to finish cancellation...
     }
 }
 
-fn prepare_write<D, E>(channel: &mut Channel, buffer: &[E], destination: &mut D)
+pub(crate) fn prepare_write<D, E>(channel: &mut Channel, buffer: &[E], destination: &mut D)
 where
     D: Destination<E>,
     E: Element,
 {
-    channel.disable();
-    channel.set_disable_on_completion(true);
-    channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
     // Safety: hardware address must be valid, otherwise impl is unsound.
     // Source buffer lifetime captured by future. The combination of minor
     // loops and transfer iterations ensure that we do not exceed the end of the
     // source.
+    cache::prepare_source(channel, buffer);
     unsafe {
+        prepare_destination(channel, destination, buffer.len());
         channel::set_source_linear_buffer(channel, buffer);
-        channel::set_destination_hardware(channel, destination.destination_address());
-        channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
-        channel.set_transfer_iterations(buffer.len() as u16);
     }
 
     destination.enable_destination();
 }
 
+/// Prepare a write of `len` elements that all read from `fill`
+///
+/// The caller is responsible for keeping `fill` borrowed for the duration
+/// of the transfer.
+#[cfg(feature = "embedded-hal-async")]
+pub(crate) fn prepare_write_fill<D, E>(
+    channel: &mut Channel,
+    fill: &E,
+    destination: &mut D,
+    len: usize,
+) where
+    D: Destination<E>,
+    E: Element,
+{
+    // Safety: see prepare_write. The fill is a single element that's
+    // never incremented.
+    cache::prepare_source(channel, core::slice::from_ref(fill));
+    unsafe {
+        prepare_destination(channel, destination, len);
+        channel::set_source_hardware(channel, fill);
+    }
+
+    destination.enable_destination();
+}
+
+/// Configure the destination side of a memory-to-peripheral transfer
+///
+/// # Safety
+///
+/// Caller must configure a source that can provide `len` elements.
+unsafe fn prepare_destination<D, E>(channel: &mut Channel, destination: &mut D, len: usize)
+where
+    D: Destination<E>,
+    E: Element,
+{
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
+    let window = destination.destination_window();
+    channel::set_destination_hardware_window(channel, destination.destination_address(), &window);
+    channel.set_minor_loop_bytes(window.minor_loop_bytes());
+    channel.set_transfer_iterations(window.requests(len));
+}
+
 /// Use a DMA channel to send a `buffer` of data to the destination peripheral.
 ///
+/// Each service request writes the destination's full [`Window`] of registers.
+///
 /// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
 /// to wake the executor when the transfer completes. Otherwise, poll the future.
 ///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the destination's window size.
+///
 /// # Example
 ///
 /// Send five bytes to a LPUART device. Wake the executor when the transfer completes.
@@ -337,10 +1213,260 @@ where
         destination,
         // Safety: transfer is correctly defined
         transfer: unsafe { Transfer::new(channel) },
+        dirty: None,
+        _elem: PhantomData,
+    }
+}
+
+/// Use a DMA channel to send a `buffer` of elements that differ in width
+/// from the destination peripheral's elements
+///
+/// `P` is the width of the destination register, and `M` is the width of the
+/// elements in memory.
+///
+/// - If `M` is narrower than `P`, each service request packs
+///   `size_of::<P>() / size_of::<M>()` elements into one register write. The
+///   buffer length must be a multiple of that ratio.
+/// - If `M` is wider than `P`, each service request unpacks one `M` into
+///   `size_of::<M>() / size_of::<P>()` register writes. The peripheral must
+///   accept that many writes per service request.
+///
+/// Otherwise, this behaves like [`write`](fn@write).
+///
+/// # Example
+///
+/// Pack bytes into 32-bit writes.
+///
+/// ```no_run
+/// use imxrt_dma::peripheral;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u32> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u32 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let mut fifo = // A DMA-capable peripheral with a 32-bit FIFO
+///     # X;
+/// let bytes = [0u8; 16];
+///
+/// // Four register writes.
+/// peripheral::write_mixed(&mut channel, &bytes, &mut fifo).await?;
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the buffer is empty, if the destination describes a [`Window`]
+/// of more than one register, or if the buffer doesn't fill a whole number
+/// of registers.
+pub fn write_mixed<'a, D, P, M>(
+    channel: &'a mut Channel,
+    buffer: &'a [M],
+    destination: &'a mut D,
+) -> Write<'a, D, P>
+where
+    D: Destination<P>,
+    P: Element,
+    M: Element,
+{
+    assert!(
+        destination.destination_window() == Window::single::<P>(),
+        "DMA mixed-width transfers need a single register"
+    );
+    let minor_bytes = core::mem::size_of::<P>().max(core::mem::size_of::<M>());
+    let bytes = core::mem::size_of_val(buffer);
+    assert!(bytes > 0, "DMA buffer is empty");
+    assert!(
+        bytes.is_multiple_of(minor_bytes),
+        "DMA buffer does not fill a whole number of registers"
+    );
+
+    cache::prepare_source(channel, buffer);
+    // Safety: see prepare_write. Each service request moves the wider of
+    // the two elements, and the service requests cover the buffer.
+    unsafe {
+        channel.disable();
+        channel.set_disable_on_completion(true);
+        channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
+        channel::set_destination_hardware(channel, destination.destination_address());
+        channel::set_source_linear_buffer(channel, buffer);
+        channel.set_minor_loop_bytes(minor_bytes as u32);
+        channel.set_transfer_iterations((bytes / minor_bytes) as u16);
+    }
+    destination.enable_destination();
+
+    Write {
+        channel,
+        destination,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        dirty: None,
+        _elem: PhantomData,
+    }
+}
+
+/// Use a DMA channel to send many `buffers` of data to the destination peripheral
+///
+/// The channel sends each buffer in turn, then resolves once. There are no gaps
+/// between the buffers. This behaves like [`write`](fn@write), except that
+/// the data comes from separate buffers. The transfer skips empty buffers.
+///
+/// Supply one [`Descriptor`] for each non-empty buffer. The transfer borrows the
+/// descriptors until it's dropped.
+///
+/// # Example
+///
+/// Send a header, a payload, and a CRC as one transfer.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Descriptor, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u8> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let mut uart = // A DMA-capable UART
+///     # X;
+/// let header = [0x7Eu8, 0x01];
+/// let payload = [0u8; 32];
+/// let crc = [0xABu8, 0xCD];
+/// let mut descriptors = [Descriptor::new(); 3];
+///
+/// peripheral::write_vectored(&mut channel, &[&header, &payload, &crc], &mut descriptors, &mut uart)
+///     .await?;
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if
+///
+/// - all buffers are empty
+/// - there are fewer descriptors than non-empty buffers
+/// - a buffer length is not a multiple of the destination's window size
+pub fn write_vectored<'a, D, E>(
+    channel: &'a mut Channel,
+    buffers: &'a [&'a [E]],
+    descriptors: &'a mut [Descriptor],
+    destination: &'a mut D,
+) -> Write<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    let descriptors = vectored_descriptors(descriptors, buffers.iter().map(|b| b.len()));
+    let window = destination.destination_window();
+    // Safety: see prepare_write. The first buffer describes the template
+    // for all descriptors. All buffers and descriptors are borrowed for 'a.
+    unsafe {
+        let first = buffers.iter().find(|b| !b.is_empty()).unwrap();
+        prepare_destination(channel, destination, first.len());
+        channel::set_source_linear_buffer(channel, first);
+        let template = channel.descriptor();
+        let nonempty = buffers.iter().filter(|b| !b.is_empty());
+        for (descriptor, buffer) in descriptors.iter_mut().zip(nonempty) {
+            cache::prepare_source(channel, buffer);
+            *descriptor = template;
+            descriptor.set_source(buffer.as_ptr(), buffer.len());
+            descriptor.set_transfer_iterations(window.requests(buffer.len()));
+        }
+        channel::set_scatter_gather(channel, descriptors, false);
+    }
+
+    destination.enable_destination();
+    Write {
+        channel,
+        destination,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        dirty: None,
         _elem: PhantomData,
     }
 }
 
+/// A memory-to-peripheral transfer that's programmed once, and run many times
+///
+/// [`new`](PreparedWrite::new) programs the DMA channel. Each [`run`](PreparedWrite::run)
+/// only enables the peripheral, and returns a fresh [`Write`] future. Use
+/// [`buffer`](PreparedWrite::buffer) to update the data between runs.
+pub struct PreparedWrite<'a, D, E> {
+    channel: &'a mut Channel,
+    buffer: &'a mut [E],
+    destination: &'a mut D,
+    /// The programmed transfer, restored before a run if needed.
+    descriptor: Descriptor,
+    /// Set while a run is incomplete, or if it didn't complete.
+    dirty: Cell<bool>,
+}
+
+impl<'a, D, E> PreparedWrite<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Program `channel` to send a `buffer` of elements to the destination peripheral
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer length is not a multiple of the destination's window size.
+    pub fn new(channel: &'a mut Channel, buffer: &'a mut [E], destination: &'a mut D) -> Self {
+        // Safety: see prepare_write. The buffer is borrowed for 'a.
+        unsafe {
+            prepare_destination(channel, destination, buffer.len());
+            channel::set_source_linear_buffer(channel, buffer);
+        }
+        PreparedWrite {
+            descriptor: channel.descriptor(),
+            dirty: Cell::new(false),
+            channel,
+            buffer,
+            destination,
+        }
+    }
+
+    /// Run the transfer
+    ///
+    /// The returned future behaves like the future returned from [`write`](fn@write).
+    /// Each run starts from the beginning of the buffer. If the previous run
+    /// didn't complete, the run first restores the programmed transfer.
+    pub fn run(&mut self) -> Write<'_, D, E> {
+        if self.dirty.replace(true) {
+            // Safety: the descriptor was programmed by new, and the buffer
+            // is borrowed for 'a.
+            unsafe { self.channel.set_descriptor(&self.descriptor) };
+        }
+        cache::prepare_source(self.channel, self.buffer);
+        self.destination.enable_destination();
+        let channel: &Channel = self.channel;
+        Write {
+            channel,
+            destination: self.destination,
+            // Safety: transfer is correctly defined
+            transfer: unsafe { Transfer::new(channel) },
+            dirty: Some(&self.dirty),
+            _elem: PhantomData,
+        }
+    }
+
+    /// Returns the buffer
+    pub fn buffer(&mut self) -> &mut [E] {
+        self.buffer
+    }
+}
+
 /// Indicates that a peripheral can read and write from a single buffer
 /// using two simultaneous DMA transfers
 ///
@@ -372,6 +1498,7 @@ where
     tx_channel: &'a Channel,
     tx_transfer: Transfer<'a>,
     tx_done: bool,
+    _cache: cache::Invalidate,
     peripheral: &'a mut P,
     _elem: PhantomData<E>,
 }
@@ -449,16 +1576,82 @@ where
 {
     prepare_write(tx_channel, buffer, peripheral);
     prepare_read(rx_channel, peripheral, buffer);
+    let invalidate = cache::Invalidate::new(rx_channel, buffer);
 
-    FullDuplex {
-        rx_channel,
-        rx_transfer: unsafe { Transfer::new(rx_channel) },
-        rx_done: false,
-        tx_channel,
-        tx_transfer: unsafe { Transfer::new(tx_channel) },
-        tx_done: false,
-        peripheral,
-        _elem: PhantomData,
+    // Safety: both transfers are correctly defined
+    unsafe { FullDuplex::new(rx_channel, tx_channel, peripheral, invalidate) }
+}
+
+impl<'a, P, E> FullDuplex<'a, P, E>
+where
+    P: Bidirectional<E>,
+    E: Element,
+{
+    /// Create a full-duplex transfer from two prepared channels
+    ///
+    /// # Safety
+    ///
+    /// Both channels must describe valid transfers with `peripheral`, and
+    /// all memory must be valid for `'a`. `invalidate` describes the receive
+    /// buffer.
+    pub(crate) unsafe fn new(
+        rx_channel: &'a Channel,
+        tx_channel: &'a Channel,
+        peripheral: &'a mut P,
+        invalidate: cache::Invalidate,
+    ) -> Self {
+        FullDuplex {
+            rx_channel,
+            rx_transfer: Transfer::new(rx_channel),
+            rx_done: false,
+            tx_channel,
+            tx_transfer: Transfer::new(tx_channel),
+            tx_done: false,
+            _cache: invalidate,
+            peripheral,
+            _elem: PhantomData,
+        }
+    }
+
+    /// Suspend the full-duplex transfer
+    ///
+    /// Suspends the transmit transfer, then the receive transfer. See
+    /// [`Transfer::suspend`] for more information.
+    pub fn suspend(mut self: Pin<&mut Self>) {
+        // Safety: pin projections OK, the transfers are never moved.
+        unsafe {
+            if !self.tx_done {
+                self.as_mut()
+                    .map_unchecked_mut(|this| &mut this.tx_transfer)
+                    .suspend();
+            }
+            if !self.rx_done {
+                self.as_mut()
+                    .map_unchecked_mut(|this| &mut this.rx_transfer)
+                    .suspend();
+            }
+        }
+    }
+
+    /// Resume a suspended full-duplex transfer
+    ///
+    /// Resumes the receive transfer, then the transmit transfer. See
+    /// [`Transfer::resume`] for more information.
+    pub fn resume(mut self: Pin<&mut Self>) {
+        // Safety: pin projections OK, the transfers are never moved.
+        unsafe {
+            self.as_mut()
+                .map_unchecked_mut(|this| &mut this.rx_transfer)
+                .resume();
+            self.as_mut()
+                .map_unchecked_mut(|this| &mut this.tx_transfer)
+                .resume();
+        }
+    }
+
+    /// Returns `true` if the full-duplex transfer is suspended
+    pub fn is_suspended(&self) -> bool {
+        self.rx_transfer.is_suspended() || self.tx_transfer.is_suspended()
     }
 }
 
@@ -508,8 +1701,8 @@ where
     fn drop(&mut self) {
         self.peripheral.disable_destination();
         self.peripheral.disable_source();
-        while self.tx_channel.is_hardware_signaling() {}
-        while self.rx_channel.is_hardware_signaling() {}
+        while self.tx_channel.is_enabled() && self.tx_channel.is_hardware_signaling() {}
+        while self.rx_channel.is_enabled() && self.rx_channel.is_hardware_signaling() {}
         // Drop the transfers to finish cancellation...
     }
 }
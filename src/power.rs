@@ -0,0 +1,108 @@
+//! Low-power support
+
+use crate::{Dma, Partition};
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+impl<const CHANNELS: usize> Dma<CHANNELS> {
+    /// Wait for the DMA controller to go idle
+    ///
+    /// The future completes once no channel is active, and no enabled channel
+    /// is receiving a service signal from hardware. Await this before entering
+    /// a low-power mode that stops the DMA clock.
+    ///
+    /// `quiesce` doesn't stop any transfers, and new transfers may start once
+    /// the future completes. The DMA controller doesn't signal when it's idle.
+    /// Instead, [`on_interrupt`](Self::on_interrupt) wakes the future whenever
+    /// a channel completes, or has an error, and the future checks again. Make
+    /// sure that each running channel interrupts on completion. Poll at most one
+    /// `Quiesce` future for each channel at a time; partitions with separate
+    /// channels may each poll their own.
+    ///
+    /// To let a channel's peripheral wake the chip from stop mode, see
+    /// [`set_async_request`](crate::channel::Channel::set_async_request).
+    ///
+    /// ```no_run
+    /// use imxrt_dma::Dma;
+    /// static DMA: Dma<32> = // Handle to DMA driver.
+    /// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// # async fn f() {
+    /// DMA.quiesce().await;
+    /// // Enter stop mode...
+    /// # }
+    /// ```
+    pub fn quiesce(&'static self) -> Quiesce<CHANNELS> {
+        Quiesce {
+            dma: self,
+            mask: u32::MAX,
+        }
+    }
+
+    /// Returns `true` if no channel is active, and no enabled channel is
+    /// receiving a service signal from hardware
+    pub fn is_quiescent(&self) -> bool {
+        self.is_quiescent_mask(u32::MAX)
+    }
+
+    /// Returns `true` if no channel in `mask` is active, or receiving a
+    /// service signal from hardware while it's enabled
+    fn is_quiescent_mask(&self, mask: u32) -> bool {
+        let busy = self.controller.ERQ.read() & self.controller.HRS.read();
+        (0..CHANNELS)
+            .filter(|channel| mask & (1 << channel) != 0)
+            .all(|channel| {
+                let active = self.controller.TCD[channel].CSR.read()
+                    & crate::ral::tcd::CSR::ACTIVE::mask
+                    != 0;
+                !active && busy & (1 << channel) == 0
+            })
+    }
+}
+
+impl<const CHANNELS: usize> Partition<CHANNELS> {
+    /// Wait for the partition's channels to go idle
+    ///
+    /// Like [`Dma::quiesce`], but only the partition's channels are checked.
+    /// Another core may still be using its channels.
+    pub fn quiesce(&'static self) -> Quiesce<CHANNELS> {
+        Quiesce {
+            dma: self.dma(),
+            mask: self.mask(),
+        }
+    }
+
+    /// Returns `true` if none of the partition's channels are active, or
+    /// receiving a service signal from hardware while they're enabled
+    pub fn is_quiescent(&self) -> bool {
+        self.dma().is_quiescent_mask(self.mask())
+    }
+}
+
+/// A future that completes once the DMA controller is idle
+///
+/// Use [`Dma::quiesce`] or [`Partition::quiesce`] to create this future.
+pub struct Quiesce<const CHANNELS: usize> {
+    dma: &'static Dma<CHANNELS>,
+    mask: u32,
+}
+
+impl<const CHANNELS: usize> Future for Quiesce<CHANNELS> {
+    type Output = ();
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        for (channel, waker) in self.dma.quiesce.iter().enumerate() {
+            if self.mask & (1 << channel) != 0 {
+                waker.register(cx.waker());
+            }
+        }
+        if self.dma.is_quiescent_mask(self.mask) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -53,6 +53,11 @@ pub struct RegisterBlock {
     pub TCD: [tcd::RegisterBlock; 32],
 }
 
+impl RegisterBlock {
+    /// Enable minor loop mapping (CR)
+    pub const EMLM: u32 = 1 << 7;
+}
+
 /// Wrapper for channel priority registers
 ///
 /// Channel priority registers cannot be accessed with
@@ -13,4 +13,5 @@ impl RegisterBlock {
     pub const ENBL: u32 = 1 << 31;
     pub const TRIG: u32 = 1 << 30;
     pub const A_ON: u32 = 1 << 29;
+    pub const SOURCE: u32 = 0x7F;
 }
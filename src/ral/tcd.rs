@@ -45,7 +45,7 @@ impl RegisterBlock {
     }
 }
 
-mod ATTR {
+pub mod ATTR {
     /// Destination data transfer size
     pub mod SIZE {
         /// Offset (0 bits)
@@ -85,6 +85,20 @@ pub mod SATTR {
 
 pub mod CSR {
 
+    /// Channel Start
+    pub mod START {
+        /// Offset (0 bits)
+        pub const offset: u16 = 0;
+        /// Mask (1 bit: 1 << 0)
+        pub const mask: u16 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
     /// Enable an interrupt when major iteration count completes.
     pub mod INTMAJOR {
         /// Offset (1 bits)
@@ -99,6 +113,20 @@ pub mod CSR {
         pub mod RW {}
     }
 
+    /// Enable an interrupt when major counter is half complete.
+    pub mod INTHALF {
+        /// Offset (2 bits)
+        pub const offset: u16 = 2;
+        /// Mask (1 bit: 1 << 2)
+        pub const mask: u16 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values
+        pub mod RW {}
+    }
+
     /// Disable Request
     pub mod DREQ {
         /// Offset (3 bits)
@@ -151,6 +179,34 @@ pub mod CSR {
         }
     }
 
+    /// Enable Scatter/Gather Processing
+    pub mod ESG {
+        /// Offset (4 bits)
+        pub const offset: u16 = 4;
+        /// Mask (1 bit: 1 << 4)
+        pub const mask: u16 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Enable channel-to-channel linking on major loop complete
+    pub mod MAJORELINK {
+        /// Offset (5 bits)
+        pub const offset: u16 = 5;
+        /// Mask (1 bit: 1 << 5)
+        pub const mask: u16 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
     /// Channel Active
     pub mod ACTIVE {
         /// Offset (6 bits)
@@ -166,6 +222,75 @@ pub mod CSR {
     }
 }
 
+pub mod NBYTES {
+    /// Source Minor Loop Offset Enable
+    ///
+    /// Only valid when minor loop mapping is enabled.
+    pub mod SMLOE {
+        /// Offset (31 bits)
+        pub const offset: u32 = 31;
+        /// Mask (1 bit: 1 << 31)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Destination Minor Loop Offset Enable
+    ///
+    /// Only valid when minor loop mapping is enabled.
+    pub mod DMLOE {
+        /// Offset (30 bits)
+        pub const offset: u32 = 30;
+        /// Mask (1 bit: 1 << 30)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Minor Loop Offset
+    ///
+    /// Only valid when SMLOE or DMLOE is set.
+    pub mod MLOFF {
+        /// Offset (10 bits)
+        pub const offset: u32 = 10;
+        /// Mask (20 bits: 0xfffff << 10)
+        pub const mask: u32 = 0xfffff << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Minor Byte Transfer Count
+    ///
+    /// The field is 10 bits when SMLOE or DMLOE is set, and 30 bits when
+    /// minor loop mapping is enabled. Otherwise, the field is the whole register.
+    pub mod NBYTES {
+        /// Offset (0 bits)
+        pub const offset: u32 = 0;
+        /// Mask (10 bits: 0x3ff << 0)
+        pub const mask: u32 = 0x3ff << offset;
+        /// Mask (30 bits), used when neither SMLOE nor DMLOE is set
+        pub const mask_mloffno: u32 = 0x3fff_ffff << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+}
+
 pub mod CITER {
     /// Current Major Iteration Count
     pub mod CITER {
@@ -196,6 +321,7 @@ pub mod BITER {
 /// Some stalls may not occur to minimize startup latency. See the
 /// reference manual for more details.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u16)]
 pub enum BandwidthControl {
     /// DMA engine stalls for 4 cycles after each R/W.
@@ -211,4 +337,11 @@ impl BandwidthControl {
             Some(bwc) => bwc as u16,
         }
     }
+    pub(crate) fn from_raw(raw: u16) -> Option<Self> {
+        match raw {
+            CSR::BWC::RW::BWC_2 => Some(Self::Stall4Cycles),
+            CSR::BWC::RW::BWC_3 => Some(Self::Stall8Cycles),
+            _ => None,
+        }
+    }
 }
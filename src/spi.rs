@@ -0,0 +1,260 @@
+//! An `embedded-hal-async` SPI bus over DMA channels.
+//!
+//! Enable the `embedded-hal-async` feature to use this module.
+//!
+//! [`SpiBus`] implements [`embedded_hal_async::spi::SpiBus`] for any
+//! [`Bidirectional`] peripheral. It uses two DMA channels: one to receive data
+//! from the peripheral, and one to send data to the peripheral. Unlike
+//! [`full_duplex`](crate::peripheral::full_duplex), the read and write buffers
+//! may have different lengths. When there's more to read than to write, the bus
+//! sends a dummy element. When there's more to write than to read, the bus
+//! discards the received data.
+
+use crate::{
+    cache,
+    channel::Channel,
+    peripheral::{self, Bidirectional, FullDuplex},
+    Element, Error,
+};
+
+use embedded_hal_async::spi::{self, ErrorKind, ErrorType};
+
+/// A SPI bus driven by two DMA channels
+///
+/// See the [module-level documentation](crate::spi) for more information.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::{peripheral, spi::SpiBus};
+/// use embedded_hal_async::spi::SpiBus as _;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+/// # unsafe impl peripheral::Destination<u8> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+/// # unsafe impl peripheral::Bidirectional<u8> for X {}
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let lpspi = // A LPSPI peripheral
+///     # X;
+/// let mut rx_channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let tx_channel = // DMA channel 8
+///     # unsafe { DMA.channel(8) };
+/// rx_channel.set_interrupt_on_completion(true);
+///
+/// let mut spi = SpiBus::new(lpspi, rx_channel, tx_channel);
+///
+/// // Send a two-byte command, then read back four bytes.
+/// let mut response = [0u8; 4];
+/// spi.write(&[0x03, 0x00]).await?;
+/// spi.read(&mut response).await?;
+/// # Ok(()) }
+/// ```
+pub struct SpiBus<P, E> {
+    peripheral: P,
+    rx_channel: Channel,
+    tx_channel: Channel,
+    /// Sent when there's nothing left to write.
+    dummy: E,
+    /// Receives data when there's nothing left to read.
+    sink: E,
+}
+
+impl<P, E> SpiBus<P, E>
+where
+    P: Bidirectional<E>,
+    E: Element + Default,
+{
+    /// Create a SPI bus from a peripheral and two DMA channels
+    ///
+    /// `rx_channel` receives data from the peripheral, and `tx_channel` sends
+    /// data to the peripheral. The dummy element defaults to zero; use
+    /// [`set_dummy`](Self::set_dummy) to change it.
+    ///
+    /// You're responsible for configuring the channel interrupts. Since the
+    /// transmit side finishes before the receive side, consider generating
+    /// an interrupt only for `rx_channel`.
+    pub fn new(peripheral: P, rx_channel: Channel, tx_channel: Channel) -> Self {
+        SpiBus {
+            peripheral,
+            rx_channel,
+            tx_channel,
+            dummy: E::default(),
+            sink: E::default(),
+        }
+    }
+}
+
+impl<P, E> SpiBus<P, E>
+where
+    P: Bidirectional<E>,
+    E: Element,
+{
+    /// Set the element that's sent when there's nothing left to write
+    pub fn set_dummy(&mut self, dummy: E) {
+        self.dummy = dummy;
+    }
+
+    /// Returns the element that's sent when there's nothing left to write
+    pub fn dummy(&self) -> E {
+        self.dummy
+    }
+
+    /// Returns the peripheral and the receive and transmit channels
+    pub fn release(self) -> (P, Channel, Channel) {
+        (self.peripheral, self.rx_channel, self.tx_channel)
+    }
+
+    /// Exchange data with the peripheral
+    ///
+    /// Both sides must describe the same number of elements.
+    async fn exchange(&mut self, rx: Rx<'_, E>, tx: Tx<'_, E>) -> Result<(), Error> {
+        debug_assert_eq!(rx.len(), tx.len());
+        if rx.len() == 0 {
+            return Ok(());
+        }
+
+        match tx {
+            Tx::Buffer(buffer) => {
+                peripheral::prepare_write(&mut self.tx_channel, buffer, &mut self.peripheral)
+            }
+            Tx::Dummy(len) => peripheral::prepare_write_fill(
+                &mut self.tx_channel,
+                &self.dummy,
+                &mut self.peripheral,
+                len,
+            ),
+        }
+        let invalidate = match rx {
+            Rx::Buffer(buffer) => {
+                peripheral::prepare_read(&mut self.rx_channel, &mut self.peripheral, buffer);
+                cache::Invalidate::new(&self.rx_channel, buffer)
+            }
+            Rx::Discard(len) => {
+                peripheral::prepare_read_discard(
+                    &mut self.rx_channel,
+                    &mut self.peripheral,
+                    &mut self.sink,
+                    len,
+                );
+                cache::Invalidate::NONE
+            }
+        };
+
+        // Safety: both transfers are defined above. Buffers, dummy, and sink
+        // are borrowed for the lifetime of the future.
+        unsafe {
+            FullDuplex::new(
+                &self.rx_channel,
+                &self.tx_channel,
+                &mut self.peripheral,
+                invalidate,
+            )
+        }
+        .await
+    }
+}
+
+/// Where received data is stored
+enum Rx<'a, E> {
+    /// Store received elements in the buffer.
+    Buffer(&'a mut [E]),
+    /// Discard this many received elements.
+    Discard(usize),
+}
+
+impl<E> Rx<'_, E> {
+    fn len(&self) -> usize {
+        match self {
+            Rx::Buffer(buffer) => buffer.len(),
+            Rx::Discard(len) => *len,
+        }
+    }
+}
+
+/// Where transmitted data comes from
+enum Tx<'a, E> {
+    /// Send elements from the buffer.
+    Buffer(&'a [E]),
+    /// Send this many dummy elements.
+    Dummy(usize),
+}
+
+impl<E> Tx<'_, E> {
+    fn len(&self) -> usize {
+        match self {
+            Tx::Buffer(buffer) => buffer.len(),
+            Tx::Dummy(len) => *len,
+        }
+    }
+}
+
+impl spi::Error for Error {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<P, E> ErrorType for SpiBus<P, E> {
+    type Error = Error;
+}
+
+impl<P, E> spi::SpiBus<E> for SpiBus<P, E>
+where
+    P: Bidirectional<E>,
+    E: Element + 'static,
+{
+    async fn read(&mut self, words: &mut [E]) -> Result<(), Self::Error> {
+        let len = words.len();
+        self.exchange(Rx::Buffer(words), Tx::Dummy(len)).await
+    }
+
+    async fn write(&mut self, words: &[E]) -> Result<(), Self::Error> {
+        self.exchange(Rx::Discard(words.len()), Tx::Buffer(words))
+            .await
+    }
+
+    async fn transfer(&mut self, read: &mut [E], write: &[E]) -> Result<(), Self::Error> {
+        let common = read.len().min(write.len());
+        let (read, read_rest) = read.split_at_mut(common);
+        let (write, write_rest) = write.split_at(common);
+
+        self.exchange(Rx::Buffer(read), Tx::Buffer(write)).await?;
+        if !read_rest.is_empty() {
+            let len = read_rest.len();
+            self.exchange(Rx::Buffer(read_rest), Tx::Dummy(len)).await
+        } else {
+            self.exchange(Rx::Discard(write_rest.len()), Tx::Buffer(write_rest))
+                .await
+        }
+    }
+
+    async fn transfer_in_place(&mut self, words: &mut [E]) -> Result<(), Self::Error> {
+        if words.is_empty() {
+            return Ok(());
+        }
+        peripheral::full_duplex(
+            &mut self.rx_channel,
+            &mut self.tx_channel,
+            &mut self.peripheral,
+            words,
+        )
+        .await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        // Every transfer waits for the final received element.
+        Ok(())
+    }
+}
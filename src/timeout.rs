@@ -0,0 +1,275 @@
+//! DMA transfers with timeouts.
+//!
+//! The functions in this module perform the same transfers as the DMA futures,
+//! but they also take a [`Clock`] and a deadline. If the transfer doesn't complete
+//! by the deadline, the future cancels the transfer, and resolves with
+//! [`Error::Expired`]. The error includes the number of elements transferred
+//! before the cancellation.
+//!
+//! Use a timeout when a peripheral may stop requesting DMA service. Without
+//! a timeout, a [`read`](crate::peripheral::read) from a silent peripheral
+//! never completes.
+//!
+//! # Example
+//!
+//! Receive data from a peripheral, waiting at most 1000 clock ticks.
+//!
+//! ```no_run
+//! use imxrt_dma::timeout::{self, Clock};
+//! use core::task::Waker;
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct Lpuart;
+//! # unsafe impl imxrt_dma::peripheral::Source<u8> for Lpuart {
+//! #   fn source_signal(&self) -> u32 { 0 }
+//! #   fn source_address(&self) -> *const u8 { core::ptr::null() }
+//! #   fn enable_source(&mut self) {}
+//! #   fn disable_source(&mut self) {}
+//! # }
+//!
+//! struct Ticks;
+//! impl Clock for Ticks {
+//!     type Instant = u32;
+//!     fn now(&self) -> u32 {
+//!         // Read a free-running timer...
+//!         # 0
+//!     }
+//!     fn wake_at(&self, deadline: u32, waker: &Waker) {
+//!         // Schedule a timer interrupt that wakes the waker...
+//!     }
+//! }
+//!
+//! # async fn f() {
+//! let mut channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! let mut lpuart = // A DMA-capable peripheral
+//!     # Lpuart;
+//! let mut buffer = [0u8; 64];
+//!
+//! let deadline = Ticks.now().wrapping_add(1000);
+//! match timeout::read(&mut channel, &mut lpuart, &mut buffer, Ticks, deadline).await {
+//!     Ok(()) => { /* Received all 64 bytes */ }
+//!     Err(timeout::Error::Expired { transferred }) => { /* Received `transferred` bytes */ }
+//!     Err(timeout::Error::Dma(err)) => panic!("{err}"),
+//! }
+//! # }
+//! ```
+
+use crate::{
+    channel::Channel,
+    memcpy::{self, Memcpy},
+    peripheral::{self, Destination, Read, Source, Write},
+    Element,
+};
+
+use core::{
+    fmt::{self, Display},
+    future::Future,
+    pin::Pin,
+    sync::atomic,
+    task::{Context, Poll, Waker},
+};
+
+/// A clock that tracks a timeout's deadline
+///
+/// Implement `Clock` on a timer. The timeout futures call [`now`](Clock::now)
+/// each time they're polled, and they call [`wake_at`](Clock::wake_at) to
+/// schedule a wakeup at the deadline.
+pub trait Clock {
+    /// A point in time
+    ///
+    /// A deadline expires once `now() >= deadline`.
+    type Instant: Copy + Ord;
+    /// Returns the current time
+    fn now(&self) -> Self::Instant;
+    /// Wake `waker` at, or after, `deadline`
+    ///
+    /// If your executor polls futures in a loop, this may wake the
+    /// waker immediately.
+    fn wake_at(&self, deadline: Self::Instant, waker: &Waker);
+}
+
+impl<C: Clock> Clock for &C {
+    type Instant = C::Instant;
+    fn now(&self) -> Self::Instant {
+        C::now(self)
+    }
+    fn wake_at(&self, deadline: Self::Instant, waker: &Waker) {
+        C::wake_at(self, deadline, waker)
+    }
+}
+
+/// An error from a transfer with a timeout
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The DMA controller reported an error.
+    Dma(crate::Error),
+    /// The deadline expired, and the transfer was cancelled.
+    Expired {
+        /// The number of elements transferred before the cancellation.
+        transferred: usize,
+    },
+}
+
+impl From<crate::Error> for Error {
+    fn from(err: crate::Error) -> Self {
+        Error::Dma(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Dma(err) => Display::fmt(err, f),
+            Error::Expired { transferred } => {
+                write!(f, "DMA timeout after {transferred} elements")
+            }
+        }
+    }
+}
+
+/// A DMA transfer with a deadline
+///
+/// Use one of the functions in this module to create a `Timeout`. Each
+/// function runs a single transfer control descriptor, never a scatter /
+/// gather chain, so the channel's iteration counts describe the whole transfer.
+pub struct Timeout<'a, F, C: Clock> {
+    future: F,
+    channel: &'a Channel,
+    clock: C,
+    deadline: C::Instant,
+    element_size: usize,
+}
+
+impl<'a, F, C: Clock> Timeout<'a, F, C> {
+    fn new<E: Element>(future: F, channel: &'a Channel, clock: C, deadline: C::Instant) -> Self {
+        debug_assert!(
+            !channel.state().control.scatter_gather,
+            "DMA timeout can't count the elements of a scatter / gather chain"
+        );
+        Timeout {
+            future,
+            channel,
+            clock,
+            deadline,
+            element_size: core::mem::size_of::<E>(),
+        }
+    }
+
+    /// Returns the number of elements moved by an incomplete transfer
+    ///
+    /// This only counts the channel's current descriptor, which is the whole
+    /// transfer. See [`Timeout`].
+    fn transferred(&self) -> usize {
+        let iterations = self
+            .channel
+            .beginning_transfer_iterations()
+            .saturating_sub(self.channel.current_transfer_iterations());
+        iterations as usize * self.channel.state().minor_loop_bytes as usize / self.element_size
+    }
+}
+
+impl<F, C> Future for Timeout<'_, F, C>
+where
+    F: Future<Output = crate::Result<()>>,
+    C: Clock,
+{
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: pin projection OK, the future is never moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        if let Poll::Ready(result) = future.as_mut().poll(cx) {
+            return Poll::Ready(result.map_err(Error::Dma));
+        }
+        if this.clock.now() < this.deadline {
+            this.clock.wake_at(this.deadline, cx.waker());
+            return Poll::Pending;
+        }
+
+        // Stop the channel, then see how far it got.
+        this.channel.cancel.store(true, atomic::Ordering::Release);
+        match future.poll(cx) {
+            Poll::Ready(Err(err)) if err.is_cancelled() => {}
+            Poll::Ready(result) => return Poll::Ready(result.map_err(Error::Dma)),
+            Poll::Pending => return Poll::Pending,
+        }
+        if this.channel.is_complete() {
+            // Completed before the cancellation took effect.
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Ready(Err(Error::Expired {
+                transferred: this.transferred(),
+            }))
+        }
+    }
+}
+
+/// Perform a DMA-powered memcpy that expires at `deadline`
+///
+/// See [`memcpy::memcpy`] for more information. Since a memcpy transfers
+/// all elements in one minor loop, an expired memcpy has either transferred
+/// every element, or none of them.
+pub fn memcpy<'a, E: Element, C: Clock>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    channel: &'a mut Channel,
+    clock: C,
+    deadline: C::Instant,
+) -> Timeout<'a, Memcpy<'a, E>, C> {
+    let memcpy = memcpy::memcpy(source, destination, channel);
+    let channel = memcpy.channel();
+    Timeout::new::<E>(memcpy, channel, clock, deadline)
+}
+
+/// Receive a `buffer` of elements from the source peripheral, expiring
+/// at `deadline`
+///
+/// See [`peripheral::read`] for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the source's window size.
+pub fn read<'a, S, E, C>(
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [E],
+    clock: C,
+    deadline: C::Instant,
+) -> Timeout<'a, Read<'a, S, E>, C>
+where
+    S: Source<E>,
+    E: Element,
+    C: Clock,
+{
+    let read = peripheral::read(channel, source, buffer);
+    let channel = read.channel();
+    Timeout::new::<E>(read, channel, clock, deadline)
+}
+
+/// Send a `buffer` of elements to the destination peripheral, expiring
+/// at `deadline`
+///
+/// See [`peripheral::write`] for more information.
+///
+/// # Panics
+///
+/// Panics if the buffer length is not a multiple of the destination's window size.
+pub fn write<'a, D, E, C>(
+    channel: &'a mut Channel,
+    buffer: &'a [E],
+    destination: &'a mut D,
+    clock: C,
+    deadline: C::Instant,
+) -> Timeout<'a, Write<'a, D, E>, C>
+where
+    D: Destination<E>,
+    E: Element,
+    C: Clock,
+{
+    let write = peripheral::write(channel, buffer, destination);
+    let channel = write.channel();
+    Timeout::new::<E>(write, channel, clock, deadline)
+}
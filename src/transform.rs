@@ -0,0 +1,271 @@
+//! DMA-powered memory transforms
+//!
+//! The transforms in this module rearrange elements as they move between
+//! two buffers. A single DMA transfer performs each transform, walking the
+//! source and destination with offsets, minor loop offsets, and last address
+//! adjustments. The CPU only starts the transfer.
+//!
+//! - [`transpose`] transposes a matrix.
+//! - [`deinterleave`] splits interleaved channels, like `L R L R ...` audio
+//!   frames, into planar channels.
+//! - [`interleave`] combines planar channels into interleaved frames.
+//!
+//! Deinterleaving and interleaving are transposes. An interleaved buffer is a
+//! matrix with one row per frame, and one column per channel. A planar buffer
+//! stores each channel's samples one after another, and it's the transpose of
+//! the interleaved matrix. Use [`chunks_exact`](slice::chunks_exact) to view
+//! each channel of a planar buffer.
+//!
+//! Transforms run on an [`AlwaysOn`](crate::channel::Configuration::AlwaysOn)
+//! channel, and they use minor loop offsets. Before you start a transform,
+//! enable minor loop mapping with [`Dma::enable_minor_loop_mapping`](crate::Dma::enable_minor_loop_mapping).
+
+use crate::{
+    cache,
+    channel::{self, Channel},
+    interrupt::{suspend_methods, Transfer},
+    Element, Error,
+};
+
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A memory transform
+///
+/// `Transform` yields when it's moved every element of the source.
+/// Use [`transpose`], [`deinterleave`], or [`interleave`] to define the transform.
+pub struct Transform<'a, E> {
+    transfer: Transfer<'a>,
+    _cache: cache::Invalidate,
+    _elem: PhantomData<(&'a E, &'a mut E)>,
+}
+
+/// Transpose the `rows` by `columns` matrix in `source` into `destination`
+///
+/// Both matrices are stored in row-major order. `source` has `rows` rows,
+/// and its length is a multiple of `rows`. After the transform, `destination`
+/// holds the `columns` by `rows` transpose.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::transform;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// DMA.enable_minor_loop_mapping();
+/// let source = [
+///     1u32, 2, 3,
+///     4, 5, 6,
+/// ];
+/// let mut destination = [0; 6];
+///
+/// transform::transpose(&source, 2, &mut destination, &mut channel).await?;
+/// assert_eq!(destination, [1, 4, 2, 5, 3, 6]);
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if
+///
+/// - minor loop mapping is not enabled
+/// - the source is empty
+/// - `rows` is zero, or the source length is not a multiple of `rows`
+/// - the destination is shorter than the source
+/// - the matrix is too large for one DMA transfer. The smaller dimension
+///   can span at most 1023 bytes, and the whole matrix at most 512KiB.
+pub fn transpose<'a, E: Element>(
+    source: &'a [E],
+    rows: usize,
+    destination: &'a mut [E],
+    channel: &'a mut Channel,
+) -> Transform<'a, E> {
+    assert!(
+        channel.minor_loop_mapping(),
+        "DMA minor loop mapping is not enabled"
+    );
+    assert!(!source.is_empty(), "DMA transpose source is empty");
+    assert!(
+        rows > 0 && source.len().is_multiple_of(rows),
+        "DMA transpose source is not a matrix of {rows} rows"
+    );
+    assert!(
+        destination.len() >= source.len(),
+        "DMA transpose destination is shorter than the source"
+    );
+    let columns = source.len() / rows;
+    prepare_transpose(source, rows, columns, destination, channel);
+
+    Transform {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        _cache: cache::Invalidate::new(channel, destination),
+        _elem: PhantomData,
+    }
+}
+
+/// Split the interleaved frames in `source` into planar `channels` in `destination`
+///
+/// Each frame in `source` holds one sample for each channel. After the transform,
+/// `destination` holds all of the first channel's samples, then all of the second
+/// channel's samples, and so on.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::transform;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// DMA.enable_minor_loop_mapping();
+/// // L R L R L R
+/// let frames = [1u16, 10, 2, 20, 3, 30];
+/// let mut planar = [0; 6];
+///
+/// transform::deinterleave(&frames, 2, &mut planar, &mut channel).await?;
+/// let mut channels = planar.chunks_exact(3);
+/// assert_eq!(channels.next(), Some(&[1, 2, 3][..]));
+/// assert_eq!(channels.next(), Some(&[10, 20, 30][..]));
+/// # Ok(()) }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the source is empty, if `channels` is zero, if the source length
+/// is not a multiple of `channels`, or if the destination is shorter than the
+/// source. See [`transpose`] for the size limits, and the minor loop mapping
+/// requirement.
+pub fn deinterleave<'a, E: Element>(
+    source: &'a [E],
+    channels: usize,
+    destination: &'a mut [E],
+    channel: &'a mut Channel,
+) -> Transform<'a, E> {
+    assert!(!source.is_empty(), "DMA deinterleave source is empty");
+    assert!(
+        channels > 0 && source.len().is_multiple_of(channels),
+        "DMA deinterleave source is not a whole number of frames"
+    );
+    transpose(source, source.len() / channels, destination, channel)
+}
+
+/// Combine the planar `channels` in `source` into interleaved frames in `destination`
+///
+/// `source` holds all of the first channel's samples, then all of the second
+/// channel's samples, and so on. After the transform, each frame in `destination`
+/// holds one sample for each channel. This is the reverse of [`deinterleave`].
+///
+/// # Panics
+///
+/// Panics if the source is empty, if `channels` is zero, if the source length
+/// is not a multiple of `channels`, or if the destination is shorter than the
+/// source. See [`transpose`] for the size limits, and the minor loop mapping
+/// requirement.
+pub fn interleave<'a, E: Element>(
+    source: &'a [E],
+    channels: usize,
+    destination: &'a mut [E],
+    channel: &'a mut Channel,
+) -> Transform<'a, E> {
+    transpose(source, channels, destination, channel)
+}
+
+/// Prepare a channel to transpose a `rows` by `columns` matrix
+///
+/// Each minor loop moves the matrix's shorter dimension. If there are fewer
+/// columns, a minor loop reads a source row, and writes a destination column.
+/// Otherwise, a minor loop reads a source column, and writes a destination row.
+/// The minor loop offset moves the strided address to the next row or column.
+fn prepare_transpose<E: Element>(
+    source: &[E],
+    rows: usize,
+    columns: usize,
+    destination: &mut [E],
+    channel: &mut Channel,
+) {
+    let size = core::mem::size_of::<E>();
+    let (minor, major) = (rows.min(columns), rows.max(columns));
+    let minor_bytes = minor * size;
+    // The strided address jumps one row in the source, or one row in the destination.
+    let stride = major * size;
+    let total = rows * columns * size;
+
+    assert!(
+        minor_bytes <= 1023,
+        "DMA transpose minor dimension exceeds 1023 bytes"
+    );
+    assert!(
+        stride <= i16::MAX as usize && major <= 0x7FFF && total <= 1 << 19,
+        "DMA transpose matrix is too large"
+    );
+
+    channel.disable();
+    channel.set_disable_on_completion(true);
+
+    cache::prepare_source(channel, source);
+    cache::prepare_destination(channel, destination);
+
+    let (stride, size, total) = (stride as i16, size as i16, total as i32);
+    let (source_offset, destination_offset, source_strided) = if columns <= rows {
+        // Read a source row, write a destination column.
+        (size, stride, false)
+    } else {
+        // Read a source column, write a destination row.
+        (stride, size, true)
+    };
+
+    // Safety: the minor loop moves `minor` elements, and there are `major`
+    // minor loops. The strided address is moved back to the next row or
+    // column after each minor loop, so the transfer stays within both buffers.
+    // The last address adjustments rewind both addresses to the start of the
+    // buffers.
+    unsafe {
+        channel.set_source_address(source.as_ptr());
+        channel.set_source_offset(source_offset);
+        channel.set_source_attributes::<E>(0);
+        channel.set_destination_address(destination.as_ptr());
+        channel.set_destination_offset(destination_offset);
+        channel.set_destination_attributes::<E>(0);
+
+        channel.set_minor_loop_bytes(minor_bytes as u32);
+        channel.set_minor_loop_offset(source_strided, !source_strided, size as i32 - total);
+        channel.set_transfer_iterations(major as u16);
+
+        // The strided address advances one element per minor loop. The
+        // linear address advances through the whole buffer.
+        let (strided_last, linear_last) = (-(major as i32) * size as i32, -total);
+        if source_strided {
+            channel.set_source_last_address_adjustment(strided_last);
+            channel.set_destination_last_address_adjustment(linear_last);
+        } else {
+            channel.set_source_last_address_adjustment(linear_last);
+            channel.set_destination_last_address_adjustment(strided_last);
+        }
+    }
+
+    channel.set_channel_configuration(channel::Configuration::AlwaysOn);
+}
+
+impl<E> Transform<'_, E> {
+    suspend_methods!("transform");
+}
+
+impl<E> Future for Transform<'_, E> {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: data not moved
+        unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)
+    }
+}
+
+// Drop handled by Transfer and Invalidate impls